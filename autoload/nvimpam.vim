@@ -56,6 +56,22 @@ endfunction
 function! nvimpam#updatefolds()
   call rpcnotify(s:jobid, 'RefreshFolds')
 endfunction
+
+function! nvimpam#validate()
+  call rpcnotify(s:jobid, 'Validate')
+endfunction
+
+function! nvimpam#gotoinclude()
+  call rpcnotify(s:jobid, 'GotoInclude', line('.') - 1)
+endfunction
+
+function! nvimpam#gotogroup()
+  call rpcnotify(s:jobid, 'GotoGroup', line('.') - 1, col('.') - 1)
+endfunction
+
+function! nvimpam#regenerateheader()
+  call rpcnotify(s:jobid, 'RegenerateHeader', line('.') - 1)
+endfunction
 "function! s:NotifyCursorMovedI()
 "  let [ bufnum, lnum, column, off ] = getpos('.')
 "  call rpcnotify(s:jobid, 'cursor-moved-i', lnum, column)
@@ -6,7 +6,9 @@ use self::test::Bencher;
 
 use nvimpam_lib::card::ges::GesType;
 use nvimpam_lib::card::keyword::Keyword;
+use nvimpam_lib::codeyard;
 use nvimpam_lib::folds::FoldList;
+use nvimpam_lib::lines::Lines;
 use nvimpam_lib::nocommentiter::CommentLess;
 
 #[bench]
@@ -43,7 +45,25 @@ fn bench_parse_str(b: &mut Bencher) {
   b.iter(|| {
     let r = test::black_box(&v);
     let _parsed: Vec<Option<Keyword>> =
-      r.iter().map(|s| Keyword::parse(s)).collect();
+      r.iter().map(Keyword::parse).collect();
+  })
+}
+
+#[bench]
+fn bench_parse_str_slices(b: &mut Bencher) {
+  use std::fs::File;
+  use std::io::{self, BufRead};
+
+  let file = File::open("files/example.pc").unwrap();
+  let v: Vec<String> = io::BufReader::new(file)
+    .lines()
+    .map(|l| l.unwrap())
+    .collect();
+
+  b.iter(|| {
+    let r = test::black_box(&v);
+    let _parsed: Vec<Option<Keyword>> =
+      r.iter().map(codeyard::parse_slices).collect();
   })
 }
 
@@ -69,3 +89,93 @@ fn bench_skip_ges(b: &mut Bencher) {
     _a = li.skip_ges(&g);
   });
 }
+
+/// The lines of `files/example.pc`, repeated to get a large deck
+fn large_deck() -> Vec<Vec<u8>> {
+  use std::fs::File;
+  use std::io::{self, BufRead};
+
+  let file = File::open("files/example.pc").unwrap();
+  let v: Vec<Vec<u8>> = io::BufReader::new(file)
+    .split(b'\n')
+    .map(|l| l.unwrap())
+    .collect();
+
+  let mut large = Vec::with_capacity(v.len() * 200);
+  for _ in 0..200 {
+    large.extend(v.iter().cloned());
+  }
+  large
+}
+
+#[bench]
+fn bench_insert_top_vec(b: &mut Bencher) {
+  let mut v = large_deck();
+
+  b.iter(|| {
+    let _ = v.splice(10..10, vec![b"NODE  /        1".to_vec()]);
+    let _ = v.splice(10..11, vec![]);
+    test::black_box(&v);
+  })
+}
+
+#[bench]
+fn bench_insert_top_lines(b: &mut Bencher) {
+  let mut l = Lines::new(large_deck());
+
+  b.iter(|| {
+    l.update(10, 10, vec![b"NODE  /        1".to_vec()]).unwrap();
+    l.update(10, 11, vec![]).unwrap();
+    test::black_box(&l);
+  })
+}
+
+#[bench]
+fn bench_refold_lines(b: &mut Bencher) {
+  let mut l = Lines::new(large_deck());
+  l.update(10, 10, vec![b"NODE  /        1".to_vec()]).unwrap();
+
+  let mut f = FoldList::new();
+  b.iter(|| {
+    let _ = f.recreate_all(test::black_box(&l));
+  })
+}
+
+#[bench]
+fn bench_update_gathered_fold(b: &mut Bencher) {
+  let node =
+    b"NODE  /        1              0.             0.5              0.";
+  let mut l = Lines::new(vec![node.to_vec(); 1_000_000]);
+
+  let mut f = FoldList::new();
+  f.recreate_all(&l).unwrap();
+  b.iter(|| {
+    l.update(500_000, 501_000, vec![node.to_vec()]).unwrap();
+    f.update(&l, 500_000, 501_000, 1).unwrap();
+    l.update(500_000, 500_001, vec![node.to_vec(); 1000]).unwrap();
+    f.update(&l, 500_000, 500_001, 1000).unwrap();
+    test::black_box(&f);
+  })
+}
+
+#[bench]
+fn bench_add_folds_large(b: &mut Bencher) {
+  let v = large_deck();
+
+  let mut f = FoldList::new();
+  b.iter(|| {
+    f.clear();
+    let _ = f.add_folds(test::black_box(&v));
+  })
+}
+
+#[bench]
+fn bench_add_folds_parallel_large(b: &mut Bencher) {
+  let v = large_deck();
+
+  let mut f = FoldList::new();
+  b.iter(|| {
+    f.clear();
+    let _ = f.add_folds_parallel(test::black_box(&v), 8);
+  })
+}
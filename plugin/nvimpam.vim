@@ -1,3 +1,7 @@
 command! -nargs=0 NvimPamConnect call nvimpam#connect()
 command! -nargs=0 NvimPamStop call nvimpam#stop()
 command! -nargs=0 NvimPamUpdateFolds call nvimpam#updatefolds()
+command! -nargs=0 NvimPamValidate call nvimpam#validate()
+command! -nargs=0 NvimPamGotoInclude call nvimpam#gotoinclude()
+command! -nargs=0 NvimPamGotoGroup call nvimpam#gotogroup()
+command! -nargs=0 NvimPamHeader call nvimpam#regenerateheader()
@@ -21,13 +21,22 @@
 //! files to their corresponding subfolders in your runtime path (check `:echo
 //! $VIMRUNTIME` to find out). You will have the commands `:NvimPamConnect` and
 //! `:NvimPamStop` to start/stop the plugin,as well as `:NvimPamUpdateFolds` to
-//! recompute and update all folds.
+//! recompute and update all folds. `:NvimPamValidate` checks the buffer and
+//! all files it includes against the card definitions and puts the problems
+//! found into the location list. `:NvimPamGotoInclude` edits the file included
+//! by the `INCLU` card under the cursor, `:NvimPamGotoGroup` jumps to the
+//! definition of the group referenced under the cursor. `:NvimPamHeader`
+//! writes a correct `$#` header above the card line under the cursor.
 //!
 //! If you want logging, set the following environment variables:
 //!
 //! * `LOG_FILE` is the path to the log file (no logging if this is empty)
 //! * `LOG_LEVEL` can be one of `error`, `warn`, `info`, `debug` and `trace`, in
-//!    ascending order of verbosity. The default is `warn`.
+//!   ascending order of verbosity. The default is `warn`.
+//!
+//! Cards that nvimpam does not know about can be defined in a TOML file, see
+//! the `schema` module of the library for the format. Set `NVIMPAM_CARDS` to
+//! the path of that file to load it on startup.
 //!
 //! `TODO`: Implement more card types than SHELL, NODE and Comment
 //!
@@ -45,6 +54,7 @@ use failure::ResultExt;
 
 use nvimpam_lib::event::Event;
 use nvimpam_lib::handler::NeovimHandler;
+use nvimpam_lib::schema;
 
 use neovim_lib::neovim::Neovim;
 use neovim_lib::neovim_api::NeovimApi;
@@ -56,17 +66,14 @@ use simplelog::{Config, Level, LevelFilter, WriteLogger};
 fn main() {
   use std::process;
 
-  match init_logging() {
-    Err(e) => {
-      eprintln!("Nvimpam: Error initializing logger: {}", e);
-      error!("Error initializing logger: {}", e);
-      for cause in e.causes() {
-        error!("Caused by: {}", cause)
-      }
-      error!("Nvimpam exiting!");
-      process::exit(1);
+  if let Err(e) = init_logging() {
+    eprintln!("Nvimpam: Error initializing logger: {}", e);
+    error!("Error initializing logger: {}", e);
+    for cause in e.causes() {
+      error!("Caused by: {}", cause)
     }
-    Ok(()) => {}
+    error!("Nvimpam exiting!");
+    process::exit(1);
   }
 
   match start_program() {
@@ -125,8 +132,16 @@ fn init_logging() -> Result<(), Error> {
 }
 
 fn start_program() -> Result<(), Error> {
+  use std::env;
+
+  if let Some(path) = env::var_os("NVIMPAM_CARDS") {
+    let num = schema::load_file(&path)
+      .context("Could not load the cards given by 'NVIMPAM_CARDS'")?;
+    info!("Loaded {} cards from {:?}", num, path);
+  }
+
   let (sender, receiver) = mpsc::channel();
-  let mut session = try!(Session::new_parent());
+  let mut session = Session::new_parent()?;
 
   session.start_event_loop_handler(NeovimHandler(sender));
   let mut nvim = Neovim::new(session);
@@ -23,3 +23,137 @@ pub enum Cell {
   /// An alternative of 2 cells
   IntegerorBlank(u8),
 }
+
+/// The value of a [`Cell`](::card::cell::Cell), as parsed from a line.
+#[derive(Debug, PartialEq)]
+pub enum Value<'a> {
+  /// The keyword of the card, as found on the line
+  Keyword(&'a str),
+  /// A fixed entry that matched the definition
+  Fixed(&'a str),
+  /// An integer
+  Integer(i64),
+  /// A float
+  Float(f64),
+  /// A string, with trailing whitespace removed
+  Str(&'a str),
+  /// A sequence of 0 and 1
+  Binary(&'a str),
+  /// The cell is empty or contains only whitespace
+  Blank,
+  /// A continuation character `&`
+  Cont,
+  /// The contents of the cell do not fit its type
+  Invalid(&'a str),
+}
+
+impl Cell {
+  /// The number of characters the cell occupies on a line
+  pub fn width(&self) -> usize {
+    use self::Cell::*;
+
+    match *self {
+      Kw => 8,
+      Fixed(s) => s.len(),
+      Cont => 1,
+      Integer(u) | Float(u) | Blank(u) | Str(u) | Binary(u)
+      | IntegerorBlank(u) => u as usize,
+    }
+  }
+
+  /// Parse the contents of a cell into a [`Value`](::card::cell::Value).
+  /// `s` needs to be the part of the line covered by the cell, which might
+  /// be shorter than its [`width`](::card::cell::Cell::width) if the line
+  /// ends early.
+  pub fn parse<'a>(&self, s: &'a str) -> Value<'a> {
+    let trimmed = s.trim();
+
+    match *self {
+      Cell::Kw => Value::Keyword(s.trim_end()),
+      Cell::Fixed(f) => {
+        if s.trim_end() == f {
+          Value::Fixed(f)
+        } else {
+          Value::Invalid(s)
+        }
+      }
+      _ if trimmed.is_empty() => Value::Blank,
+      Cell::Integer(_) | Cell::IntegerorBlank(_) => {
+        match trimmed.parse::<i64>() {
+          Ok(i) => Value::Integer(i),
+          Err(_) => Value::Invalid(s),
+        }
+      }
+      Cell::Float(_) => match parse_float(trimmed) {
+        Some(f) => Value::Float(f),
+        None => Value::Invalid(s),
+      },
+      Cell::Str(_) => Value::Str(s.trim_end()),
+      Cell::Binary(_) => {
+        if trimmed.bytes().all(|b| b == b'0' || b == b'1') {
+          Value::Binary(trimmed)
+        } else {
+          Value::Invalid(s)
+        }
+      }
+      Cell::Cont if trimmed == "&" => Value::Cont,
+      Cell::Cont | Cell::Blank(_) => Value::Invalid(s),
+    }
+  }
+}
+
+/// Parse a float the way Pamcrash reads it. Fortran-style exponents using `D`
+/// instead of `E` are accepted.
+fn parse_float(s: &str) -> Option<f64> {
+  match s.parse::<f64>() {
+    Ok(f) => Some(f),
+    Err(_) if s.contains(&['D', 'd'][..]) => {
+      s.replace(&['D', 'd'][..], "E").parse::<f64>().ok()
+    }
+    Err(_) => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use card::cell::Cell::*;
+  use card::cell::Value;
+
+  #[test]
+  fn cells_parse_numbers() {
+    assert_eq!(Value::Integer(1234), Integer(8).parse("    1234"));
+    assert_eq!(Value::Integer(-3), IntegerorBlank(8).parse("  -3    "));
+    assert_eq!(Value::Float(0.), Float(16).parse("              0."));
+    assert_eq!(Value::Float(7.85E-6), Float(16).parse("         7.85E-6"));
+    assert_eq!(Value::Float(1000.), Float(10).parse("     1.D3 "));
+    assert_eq!(Value::Invalid("   12a"), Integer(8).parse("   12a"));
+    assert_eq!(Value::Invalid(" 0.1.2"), Float(8).parse(" 0.1.2"));
+  }
+
+  #[test]
+  fn cells_parse_blanks() {
+    assert_eq!(Value::Blank, Integer(8).parse("        "));
+    assert_eq!(Value::Blank, Float(8).parse(""));
+    assert_eq!(Value::Blank, Blank(8).parse("   "));
+    assert_eq!(Value::Invalid("  x "), Blank(4).parse("  x "));
+  }
+
+  #[test]
+  fn cells_parse_strings() {
+    assert_eq!(Value::Str("Box section"), Str(76).parse("Box section    "));
+    assert_eq!(Value::Fixed("NAME"), Fixed("NAME").parse("NAME"));
+    assert_eq!(Value::Invalid("NAMX"), Fixed("NAME").parse("NAMX"));
+    assert_eq!(Value::Keyword("NODE  /"), Kw.parse("NODE  / "));
+    assert_eq!(Value::Binary("110111"), Binary(8).parse("  110111"));
+    assert_eq!(Value::Invalid("  110121"), Binary(8).parse("  110121"));
+    assert_eq!(Value::Cont, Cont.parse("&"));
+  }
+
+  #[test]
+  fn cells_have_widths() {
+    assert_eq!(8, Kw.width());
+    assert_eq!(8, Fixed("END_PART").width());
+    assert_eq!(1, Cont.width());
+    assert_eq!(16, Float(16).width());
+  }
+}
@@ -0,0 +1,85 @@
+//! Metadata about the cells of a line, as shown in the `$#` header comments
+//! of an input file.
+
+/// The name, description, unit and default value of a
+/// [`Cell`](::card::cell::Cell). The name is the one used in the `$#` header
+/// comments, it is empty for cells that do not get a name there (e.g. the
+/// keyword).
+///
+/// The unit is given as a dimension, like `"length"` or `"mass/length^3"`,
+/// since the concrete unit depends on the model (see
+/// [`ModelSettings::unit`](::settings::ModelSettings::unit)). The default is
+/// the value Pamcrash uses if the cell is left blank.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Field {
+  pub name: &'static str,
+  pub description: &'static str,
+  pub unit: Option<&'static str>,
+  pub default: Option<&'static str>,
+}
+
+impl Field {
+  /// A field without unit and default value
+  pub const fn new(name: &'static str, description: &'static str) -> Field {
+    Field {
+      name,
+      description,
+      unit: None,
+      default: None,
+    }
+  }
+
+  /// Set the unit of the field
+  pub const fn unit(self, unit: &'static str) -> Field {
+    Field {
+      name: self.name,
+      description: self.description,
+      unit: Some(unit),
+      default: self.default,
+    }
+  }
+
+  /// Set the default value of the field
+  pub const fn default(self, default: &'static str) -> Field {
+    Field {
+      name: self.name,
+      description: self.description,
+      unit: self.unit,
+      default: Some(default),
+    }
+  }
+}
+
+/// The keyword at the start of a card
+pub const KEYWORD: Field = Field::new("", "Keyword");
+/// The fixed `NAME` at the start of a title line
+pub const NAME: Field = Field::new("", "Start of the title line");
+/// The title of an entity
+pub const TITLE: Field = Field::new("TITLE", "Title");
+/// A cell that needs to be left blank
+pub const BLANK: Field = Field::new("BLANK", "Unused, leave blank");
+/// The continuation character `&`
+pub const CONT: Field = Field::new("", "Continuation character");
+/// The ID of an element
+pub const IDEL: Field = Field::new("IDEL", "Element ID");
+/// The ID of a part
+pub const IDPRT: Field = Field::new("IDPRT", "Part ID");
+/// The ID of a frame
+pub const IFRA: Field =
+  Field::new("IFRA", "Frame ID, global frame if 0").default("0");
+
+#[cfg(test)]
+mod tests {
+  use card::field::{Field, IFRA};
+
+  #[test]
+  fn fields_can_be_built() {
+    let f = Field::new("X", "X coordinate").unit("length").default("0.");
+
+    assert_eq!("X", f.name);
+    assert_eq!(Some("length"), f.unit);
+    assert_eq!(Some("0."), f.default);
+    assert_eq!(None, Field::new("IDNOD", "Node ID").unit);
+    assert_eq!(Some("0"), IFRA.default);
+  }
+}
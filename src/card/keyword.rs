@@ -2,10 +2,13 @@
 //! classify lines according to what card type they belong to. The term
 //! "Keyword" is from the FEM solver Pamcrash, but generally used among FEM
 //! solvers.
+use schema;
 
 /// An enum to denote the several types of cards a line might belong to. For now
 /// carries only information equivalent to the keyword, not the subtypes, e.g.
-/// CNTAC types 33 and 36 will both be denoted by type Cntac
+/// CNTAC types 33 and 36 will both be denoted by type Cntac. The layout of the
+/// subtypes is given by a [`Switch`](::card::line::Line::Switch) in the
+/// definition of the card.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum Keyword {
   // Node
@@ -70,95 +73,423 @@ pub enum Keyword {
   PartLlink,
   PartPlink,
   PartGap,
+  // Material
+  Mater,
+  // Boundary conditions
+  Bounc,
+  Displ,
+  Invel,
+  // Constraints
+  Rbody,
+  // Groups
+  Group,
+  // Contact
+  Cntac,
+  // Functions
+  Funct,
+  // Frames
+  Frame,
+  // Output
+  Thnod,
+  Thloc,
+  Secfo,
+  // Control
+  Inputversion,
+  Analysis,
+  Solver,
+  Unit,
+  Signal,
+  Title,
+  Inclu,
+  Runend,
+  Octrl,
+  Ctrl,
+  /// A card loaded from a schema file, see the [`schema`](::schema) module.
+  /// The number is the index of the card in the registry.
+  Custom(u16),
 }
 
 impl Keyword {
   /// Parse a string to determine if it starts with the keyword of a card.
+  /// Keywords of cards loaded from a schema file are checked after the
+  /// built-in ones.
+  ///
+  /// The first 8 bytes of the line are read as a single `u64`, which is
+  /// matched against the words of all keywords. For `PART` cards, the bytes
+  /// 16 to 24 are matched against the words of the part types. This is
+  /// faster than matching the slices themselves, see `benches/card.rs`.
   #[inline]
-  pub fn parse<T: AsRef<str>>(s: &T) -> Option<Keyword> {
+  pub fn parse<T: AsRef<[u8]>>(s: &T) -> Option<Keyword> {
     use self::Keyword::*;
 
-    let s = s.as_ref().as_bytes();
-    let len = s.len();
-
-    if len == 0 || len < 8 {
-      None
-    } else {
-      let start = &s[0..8];
-
-      match start {
-        // Node
-        b"NODE  / " => Some(Node),
-        b"CNODE / " => Some(Cnode),
-        b"MASS  / " => Some(Mass),
-        b"NSMAS / " => Some(Nsmas),
-        b"NSMAS2/ " => Some(Nsmas2),
-        // Element
-        b"SOLID / " => Some(Solid),
-        b"HEXA20/ " => Some(Hexa20),
-        b"PENT15/ " => Some(Pent15),
-        b"PENTA6/ " => Some(Penta6),
-        b"TETR10/ " => Some(Tetr10),
-        b"TETR4 / " => Some(Tetr4),
-        b"BSHEL / " => Some(Bshel),
-        b"TSHEL / " => Some(Tshel),
-        b"SHELL / " => Some(Shell),
-        b"SHEL6 / " => Some(Shel6),
-        b"SHEL8 / " => Some(Shel8),
-        b"MEMBR / " => Some(Membr),
-        b"BEAM  / " => Some(Beam),
-        b"SPRGBM/ " => Some(Sprgbm),
-        b"BAR   / " => Some(Bar),
-        b"SPRING/ " => Some(Spring),
-        b"JOINT / " => Some(Joint),
-        b"KJOIN / " => Some(Kjoin),
-        b"MTOJNT/ " => Some(Mtojnt),
-        b"SPHEL / " => Some(Sphel),
-        b"SPHELO/ " => Some(Sphelo),
-        b"GAP   / " => Some(Gap),
-        b"IMPMA / " => Some(Impma),
-        // Link
-        b"ELINK / " => Some(Elink),
-        b"LLINK / " => Some(Llink),
-        b"SLINK / " => Some(Slink),
-        b"PLINK / " => Some(Plink),
-        b"TIED  / " => Some(Tied),
-        b"PART  / " => {
-          if len < 24 {
-            None
-          } else {
-            let p = &s[16..24];
-
-            match p {
-              b"   SOLID" => Some(PartSolid),
-              b"   BSHEL" => Some(PartBshel),
-              b"   TETRA" => Some(PartTetra),
-              b"   SPHEL" => Some(PartSphel),
-              b"   COS3D" => Some(PartCos3d),
-              b"   TSHEL" => Some(PartTshel),
-              b"   SHELL" => Some(PartShell),
-              b"   MEMBR" => Some(PartMembr),
-              b"     BAR" => Some(PartBar),
-              b"    BEAM" => Some(PartBeam),
-              b"  SPRING" => Some(PartSpring),
-              b"  SPRGBM" => Some(PartSprgbm),
-              b"   MBSPR" => Some(PartMbspr),
-              b"   JOINT" => Some(PartJoint),
-              b"   KJOIN" => Some(PartKjoin),
-              b"  MTOJNT" => Some(PartMtojnt),
-              b"   MBKJN" => Some(PartMbkjn),
-              b"    TIED" => Some(PartTied),
-              b"   SLINK" => Some(PartSlink),
-              b"   ELINK" => Some(PartElink),
-              b"   LLINK" => Some(PartLlink),
-              b"   PLINK" => Some(PartPlink),
-              b"     GAP" => Some(PartGap),
-              _ => None,
-            }
-          }
-        }
-        _ => None,
-      }
+    let s = s.as_ref();
+    let w = word_at(s, 0)?;
+
+    match w {
+      // Node
+      NODE => Some(Node),
+      CNODE => Some(Cnode),
+      MASS => Some(Mass),
+      NSMAS => Some(Nsmas),
+      NSMAS2 => Some(Nsmas2),
+      // Element
+      SOLID => Some(Solid),
+      HEXA20 => Some(Hexa20),
+      PENT15 => Some(Pent15),
+      PENTA6 => Some(Penta6),
+      TETR10 => Some(Tetr10),
+      TETR4 => Some(Tetr4),
+      BSHEL => Some(Bshel),
+      TSHEL => Some(Tshel),
+      SHELL => Some(Shell),
+      SHEL6 => Some(Shel6),
+      SHEL8 => Some(Shel8),
+      MEMBR => Some(Membr),
+      BEAM => Some(Beam),
+      SPRGBM => Some(Sprgbm),
+      BAR => Some(Bar),
+      SPRING => Some(Spring),
+      JOINT => Some(Joint),
+      KJOIN => Some(Kjoin),
+      MTOJNT => Some(Mtojnt),
+      SPHEL => Some(Sphel),
+      SPHELO => Some(Sphelo),
+      GAP => Some(Gap),
+      IMPMA => Some(Impma),
+      // Link
+      ELINK => Some(Elink),
+      LLINK => Some(Llink),
+      SLINK => Some(Slink),
+      PLINK => Some(Plink),
+      TIED => Some(Tied),
+      // Material
+      MATER => Some(Mater),
+      // Boundary conditions
+      BOUNC => Some(Bounc),
+      DISPL => Some(Displ),
+      INVEL => Some(Invel),
+      // Constraints
+      RBODY => Some(Rbody),
+      // Groups
+      GROUP => Some(Group),
+      // Contact
+      CNTAC => Some(Cntac),
+      // Functions
+      FUNCT => Some(Funct),
+      // Frames
+      FRAME => Some(Frame),
+      // Output
+      THNOD => Some(Thnod),
+      THLOC => Some(Thloc),
+      SECFO => Some(Secfo),
+      // Control
+      INPUTVERSION => Some(Inputversion),
+      ANALYSIS => Some(Analysis),
+      SOLVER => Some(Solver),
+      UNIT => Some(Unit),
+      SIGNAL => Some(Signal),
+      TITLE => Some(Title),
+      INCLU => Some(Inclu),
+      RUNEND => Some(Runend),
+      OCTRL => Some(Octrl),
+      CTRL => Some(Ctrl),
+      // Part
+      PART => match word_at(s, 16) {
+        Some(PART_SOLID) => Some(PartSolid),
+        Some(PART_BSHEL) => Some(PartBshel),
+        Some(PART_TETRA) => Some(PartTetra),
+        Some(PART_SPHEL) => Some(PartSphel),
+        Some(PART_COS3D) => Some(PartCos3d),
+        Some(PART_TSHEL) => Some(PartTshel),
+        Some(PART_SHELL) => Some(PartShell),
+        Some(PART_MEMBR) => Some(PartMembr),
+        Some(PART_BAR) => Some(PartBar),
+        Some(PART_BEAM) => Some(PartBeam),
+        Some(PART_SPRING) => Some(PartSpring),
+        Some(PART_SPRGBM) => Some(PartSprgbm),
+        Some(PART_MBSPR) => Some(PartMbspr),
+        Some(PART_JOINT) => Some(PartJoint),
+        Some(PART_KJOIN) => Some(PartKjoin),
+        Some(PART_MTOJNT) => Some(PartMtojnt),
+        Some(PART_MBKJN) => Some(PartMbkjn),
+        Some(PART_TIED) => Some(PartTied),
+        Some(PART_SLINK) => Some(PartSlink),
+        Some(PART_ELINK) => Some(PartElink),
+        Some(PART_LLINK) => Some(PartLlink),
+        Some(PART_PLINK) => Some(PartPlink),
+        Some(PART_GAP) => Some(PartGap),
+        Some(_) => schema::keyword(s),
+        None => None,
+      },
+      _ => schema::keyword(s),
+    }
+  }
+}
+
+/// The 8 bytes of a keyword as a big-endian number, so they can be compared
+/// at once
+const fn word(b: &[u8; 8]) -> u64 {
+  u64::from_be_bytes(*b)
+}
+
+/// The word of the 8 bytes of `s` starting at
+/// `start`, or `None` if `s` is too short
+#[inline]
+fn word_at(s: &[u8], start: usize) -> Option<u64> {
+  let mut b = [0; 8];
+  b.copy_from_slice(s.get(start..start + 8)?);
+  Some(u64::from_be_bytes(b))
+}
+
+// Node
+const NODE: u64 = word(b"NODE  / ");
+const CNODE: u64 = word(b"CNODE / ");
+const MASS: u64 = word(b"MASS  / ");
+const NSMAS: u64 = word(b"NSMAS / ");
+const NSMAS2: u64 = word(b"NSMAS2/ ");
+
+// Element
+const SOLID: u64 = word(b"SOLID / ");
+const HEXA20: u64 = word(b"HEXA20/ ");
+const PENT15: u64 = word(b"PENT15/ ");
+const PENTA6: u64 = word(b"PENTA6/ ");
+const TETR10: u64 = word(b"TETR10/ ");
+const TETR4: u64 = word(b"TETR4 / ");
+const BSHEL: u64 = word(b"BSHEL / ");
+const TSHEL: u64 = word(b"TSHEL / ");
+const SHELL: u64 = word(b"SHELL / ");
+const SHEL6: u64 = word(b"SHEL6 / ");
+const SHEL8: u64 = word(b"SHEL8 / ");
+const MEMBR: u64 = word(b"MEMBR / ");
+const BEAM: u64 = word(b"BEAM  / ");
+const SPRGBM: u64 = word(b"SPRGBM/ ");
+const BAR: u64 = word(b"BAR   / ");
+const SPRING: u64 = word(b"SPRING/ ");
+const JOINT: u64 = word(b"JOINT / ");
+const KJOIN: u64 = word(b"KJOIN / ");
+const MTOJNT: u64 = word(b"MTOJNT/ ");
+const SPHEL: u64 = word(b"SPHEL / ");
+const SPHELO: u64 = word(b"SPHELO/ ");
+const GAP: u64 = word(b"GAP   / ");
+const IMPMA: u64 = word(b"IMPMA / ");
+
+// Link
+const ELINK: u64 = word(b"ELINK / ");
+const LLINK: u64 = word(b"LLINK / ");
+const SLINK: u64 = word(b"SLINK / ");
+const PLINK: u64 = word(b"PLINK / ");
+const TIED: u64 = word(b"TIED  / ");
+
+// Material
+const MATER: u64 = word(b"MATER / ");
+
+// Boundary conditions
+const BOUNC: u64 = word(b"BOUNC / ");
+const DISPL: u64 = word(b"DISPL / ");
+const INVEL: u64 = word(b"INVEL / ");
+
+// Constraints
+const RBODY: u64 = word(b"RBODY / ");
+
+// Groups
+const GROUP: u64 = word(b"GROUP / ");
+
+// Contact
+const CNTAC: u64 = word(b"CNTAC / ");
+
+// Functions
+const FUNCT: u64 = word(b"FUNCT / ");
+
+// Frames
+const FRAME: u64 = word(b"FRAME / ");
+
+// Output
+const THNOD: u64 = word(b"THNOD / ");
+const THLOC: u64 = word(b"THLOC / ");
+const SECFO: u64 = word(b"SECFO / ");
+
+// Control
+const INPUTVERSION: u64 = word(b"INPUTVER");
+const ANALYSIS: u64 = word(b"ANALYSIS");
+const SOLVER: u64 = word(b"SOLVER  ");
+const UNIT: u64 = word(b"UNIT    ");
+const SIGNAL: u64 = word(b"SIGNAL  ");
+const TITLE: u64 = word(b"TITLE / ");
+const INCLU: u64 = word(b"INCLU / ");
+const RUNEND: u64 = word(b"RUNEND/ ");
+const OCTRL: u64 = word(b"OCTRL / ");
+const CTRL: u64 = word(b"CTRL  / ");
+
+// Part
+const PART: u64 = word(b"PART  / ");
+const PART_SOLID: u64 = word(b"   SOLID");
+const PART_BSHEL: u64 = word(b"   BSHEL");
+const PART_TETRA: u64 = word(b"   TETRA");
+const PART_SPHEL: u64 = word(b"   SPHEL");
+const PART_COS3D: u64 = word(b"   COS3D");
+const PART_TSHEL: u64 = word(b"   TSHEL");
+const PART_SHELL: u64 = word(b"   SHELL");
+const PART_MEMBR: u64 = word(b"   MEMBR");
+const PART_BAR: u64 = word(b"     BAR");
+const PART_BEAM: u64 = word(b"    BEAM");
+const PART_SPRING: u64 = word(b"  SPRING");
+const PART_SPRGBM: u64 = word(b"  SPRGBM");
+const PART_MBSPR: u64 = word(b"   MBSPR");
+const PART_JOINT: u64 = word(b"   JOINT");
+const PART_KJOIN: u64 = word(b"   KJOIN");
+const PART_MTOJNT: u64 = word(b"  MTOJNT");
+const PART_MBKJN: u64 = word(b"   MBKJN");
+const PART_TIED: u64 = word(b"    TIED");
+const PART_SLINK: u64 = word(b"   SLINK");
+const PART_ELINK: u64 = word(b"   ELINK");
+const PART_LLINK: u64 = word(b"   LLINK");
+const PART_PLINK: u64 = word(b"   PLINK");
+const PART_GAP: u64 = word(b"     GAP");
+
+#[cfg(test)]
+mod tests {
+  use card::keyword::Keyword::{self, *};
+  use codeyard::parse_slices;
+
+  const LINES: [&str; 12] = [
+    "NODE  /        1              0.             0.5              0.",
+    "PART  /        1   SHELL",
+    "PART  /        1  SPRGBM       0",
+    "PART  /        1   SHELX",
+    "PART  /        1  SHEL",
+    "INPUTVERSION 2011",
+    "SOLVER  CRASH",
+    "$NODE  /",
+    "NODE / ",
+    "NODE  ",
+    "",
+    "RUNEND/",
+  ];
+
+  #[test]
+  fn keywords_are_parsed() {
+    let parsed: Vec<Option<Keyword>> =
+      LINES.iter().map(Keyword::parse).collect();
+
+    assert_eq!(
+      vec![
+        Some(Node),
+        Some(PartShell),
+        Some(PartSprgbm),
+        None,
+        None,
+        Some(Inputversion),
+        Some(Solver),
+        None,
+        None,
+        None,
+        None,
+        None,
+      ],
+      parsed
+    );
+  }
+
+  const KEYWORDS: [&str; 78] = [
+    "NODE  /        1",
+    "CNODE /        1",
+    "MASS  /        1",
+    "NSMAS /        1",
+    "NSMAS2/        1",
+    "SOLID /        1",
+    "HEXA20/        1",
+    "PENT15/        1",
+    "PENTA6/        1",
+    "TETR10/        1",
+    "TETR4 /        1",
+    "BSHEL /        1",
+    "TSHEL /        1",
+    "SHELL /        1",
+    "SHEL6 /        1",
+    "SHEL8 /        1",
+    "MEMBR /        1",
+    "BEAM  /        1",
+    "SPRGBM/        1",
+    "BAR   /        1",
+    "SPRING/        1",
+    "JOINT /        1",
+    "KJOIN /        1",
+    "MTOJNT/        1",
+    "SPHEL /        1",
+    "SPHELO/        1",
+    "GAP   /        1",
+    "IMPMA /        1",
+    "ELINK /        1",
+    "LLINK /        1",
+    "SLINK /        1",
+    "PLINK /        1",
+    "TIED  /        1",
+    "MATER /        1",
+    "BOUNC /        1",
+    "DISPL /        1",
+    "INVEL /        1",
+    "RBODY /        1",
+    "GROUP /        1",
+    "CNTAC /        1",
+    "FUNCT /        1",
+    "FRAME /        1",
+    "THNOD /        1",
+    "THLOC /        1",
+    "SECFO /        1",
+    "INPUTVER       1",
+    "ANALYSIS       1",
+    "SOLVER         1",
+    "UNIT           1",
+    "SIGNAL         1",
+    "TITLE /        1",
+    "INCLU /        1",
+    "RUNEND/        1",
+    "OCTRL /        1",
+    "CTRL  /        1",
+    "PART  /        1   SOLID",
+    "PART  /        1   BSHEL",
+    "PART  /        1   TETRA",
+    "PART  /        1   SPHEL",
+    "PART  /        1   COS3D",
+    "PART  /        1   TSHEL",
+    "PART  /        1   SHELL",
+    "PART  /        1   MEMBR",
+    "PART  /        1     BAR",
+    "PART  /        1    BEAM",
+    "PART  /        1  SPRING",
+    "PART  /        1  SPRGBM",
+    "PART  /        1   MBSPR",
+    "PART  /        1   JOINT",
+    "PART  /        1   KJOIN",
+    "PART  /        1  MTOJNT",
+    "PART  /        1   MBKJN",
+    "PART  /        1    TIED",
+    "PART  /        1   SLINK",
+    "PART  /        1   ELINK",
+    "PART  /        1   LLINK",
+    "PART  /        1   PLINK",
+    "PART  /        1     GAP",
+  ];
+
+  #[test]
+  fn all_keywords_are_parsed() {
+    for line in KEYWORDS.iter() {
+      assert!(Keyword::parse(line).is_some(), "{}", line);
+      assert_eq!(parse_slices(line), Keyword::parse(line));
+    }
+  }
+
+  #[test]
+  fn words_match_slices() {
+    use std::fs;
+
+    let example = fs::read("files/example.pc").unwrap();
+
+    for line in example
+      .split(|b| *b == b'\n')
+      .chain(LINES.iter().map(|l| l.as_bytes()))
+    {
+      assert_eq!(parse_slices(&line), Keyword::parse(&line));
     }
   }
 }
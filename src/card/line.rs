@@ -2,8 +2,10 @@
 //! of a Pamcrash input file. Might not really be a line (see GES).
 use std::ops::Range;
 use std::cmp;
+use std::str;
 
-use card::cell::Cell;
+use card::cell::{Cell, Value};
+use card::field::Field;
 use card::ges::GesType;
 
 /// A line inside a card in a Pamcrash input file.
@@ -27,6 +29,61 @@ pub enum Line {
   /// The [number of repeats](::card::line::CondResult) is given as an index,
   /// see the doc for [`Optional`](::card::line::Line)
   Repeat(&'static [Cell], u8),
+  /// A group of lines that is repeated
+  ///
+  /// The number of repeats is given as an index, see the doc for
+  /// [`Repeat`](::card::line::Line). The lines of the group should not
+  /// provide conditionals themselves.
+  RepeatGroup(LineGroup, u8),
+  /// A group of lines that is repeated until a line starting with the given
+  /// terminator (e.g. `END`), ignoring leading whitespace. The terminator
+  /// line belongs to the card, and is only recognized before the first line
+  /// of the group. A line starting with a keyword also ends the repetition,
+  /// but leaves it unterminated.
+  RepeatUntil(LineGroup, &'static str),
+  /// Free-form lines, up to and including a line starting with the given
+  /// terminator (e.g. `END_OCTRL`). A line starting with a keyword also ends
+  /// the block, but leaves it unterminated.
+  Block(&'static str),
+  /// Several layouts, selected by the result of a
+  /// [`Number`](::card::line::Conditional::Number) conditional
+  ///
+  /// The conditional is given as an index, see the doc for
+  /// [`Optional`](::card::line::Line). If no [`Case`](::card::line::Case)
+  /// matches, no lines are added to the card.
+  Switch(u8, &'static [Case]),
+  /// Several layouts, selected by a conditional evaluated on the current line
+  /// itself
+  ///
+  /// The lines of the matching [`Case`](::card::line::Case) are walked
+  /// starting with the current line, so its first line should contain the
+  /// cell or char that is checked. The conditional needs to evaluate to a
+  /// [`Number`](::card::line::CondResult::Number), e.g.
+  /// [`Number`](::card::line::Conditional::Number) or
+  /// [`Char`](::card::line::Conditional::Char). If no case matches, no lines
+  /// are added to the card.
+  Select(Conditional, &'static [Case]),
+}
+
+/// A layout of a [`Switch`](::card::line::Line::Switch) or a
+/// [`Select`](::card::line::Line::Select), used if the number of the
+/// conditional equals `value`. `fields` holds the
+/// [`Field`](::card::field::Field)s of `lines`, see
+/// [`Card`](::card::Card).
+#[derive(Debug, PartialEq)]
+pub struct Case {
+  pub value: usize,
+  pub lines: &'static [Line],
+  pub fields: &'static [&'static [Field]],
+}
+
+/// The lines of a [`RepeatGroup`](::card::line::Line::RepeatGroup) or a
+/// [`RepeatUntil`](::card::line::Line::RepeatUntil). `fields` holds the
+/// [`Field`](::card::field::Field)s of `lines`, see [`Card`](::card::Card).
+#[derive(Debug, PartialEq)]
+pub struct LineGroup {
+  pub lines: &'static [Line],
+  pub fields: &'static [&'static [Field]],
 }
 
 /// An enum to represent different conditionals on lines
@@ -38,6 +95,102 @@ pub enum Conditional {
   Int(Range<usize>, u8),
   // Read a number from a given cell
   Number(Range<usize>),
+  // Read a count of values from a given cell, of which the second number fit
+  // on one line. Evaluates to the number of lines needed for the values.
+  Count(Range<usize>, u8),
+  // Read the char at the given index (0-based!). Evaluates to its code point,
+  // so a `Case` can match it with e.g. `value: 'A' as usize`.
+  Char(u8),
+}
+
+/// A [`Cell`](::card::cell::Cell) of a line, together with its
+/// [`Value`](::card::cell::Value) and the range of columns it covers
+/// (0-based, end exclusive). The range is the one given by the definition of
+/// the cell, the line might end before that.
+#[derive(Debug, PartialEq)]
+pub struct ParsedCell<'a> {
+  pub cell: &'static Cell,
+  pub range: Range<usize>,
+  pub value: Value<'a>,
+}
+
+/// A line of a card, parsed according to the
+/// [`Line`](::card::line::Line) it belongs to. `index` is the index of the
+/// line in the buffer. For a GES or a block, every line gets an own
+/// `ParsedLine`, with empty `cells`.
+#[derive(Debug, PartialEq)]
+pub struct ParsedLine<'a> {
+  pub index: usize,
+  pub line: &'static Line,
+  pub cells: Vec<ParsedCell<'a>>,
+}
+
+impl Line {
+  /// The cells of the line. Empty for a GES, a block, a switch, a select or
+  /// a repeated group.
+  pub fn cells(&self) -> &'static [Cell] {
+    match *self {
+      Line::Cells(c)
+      | Line::Provides(c, _)
+      | Line::Optional(c, _)
+      | Line::Repeat(c, _) => c,
+      Line::Ges(_)
+      | Line::Block(_)
+      | Line::Switch(..)
+      | Line::Select(..)
+      | Line::RepeatGroup(..)
+      | Line::RepeatUntil(..) => &[],
+    }
+  }
+
+  /// Split a line into the cells of this `Line` and parse them. Cells
+  /// starting after the end of the line are returned as
+  /// [`Blank`](::card::cell::Value::Blank), text after the last cell is
+  /// ignored. A cell that is not valid UTF-8 is
+  /// [`Invalid`](::card::cell::Value::Invalid), containing its valid start.
+  pub fn parse<'a, T>(&self, line: &'a T) -> Vec<ParsedCell<'a>>
+  where
+    T: 'a + AsRef<[u8]> + ?Sized,
+  {
+    let line = line.as_ref();
+    let cells = self.cells();
+    let mut v = Vec::with_capacity(cells.len());
+    let mut start = 0;
+    let continues = |i: usize| line.get(i).is_some_and(|b| b & 0xC0 == 0x80);
+
+    for cell in cells {
+      let end = start + cell.width();
+
+      // Widen the slice if a multibyte char crosses the cell boundaries
+      let mut lower = cmp::min(start, line.len());
+      while lower > 0 && continues(lower) {
+        lower -= 1;
+      }
+      let mut upper = cmp::min(end, line.len());
+      while continues(upper) {
+        upper += 1;
+      }
+
+      let value = match str::from_utf8(&line[lower..upper]) {
+        Ok(s) => cell.parse(s),
+        Err(e) => Value::Invalid(valid_start(&line[lower..upper], &e)),
+      };
+
+      v.push(ParsedCell {
+        cell,
+        range: start..end,
+        value,
+      });
+      start = end;
+    }
+
+    v
+  }
+}
+
+/// The valid UTF-8 at the start of `bytes`, given the error of decoding them
+fn valid_start<'a>(bytes: &'a [u8], e: &str::Utf8Error) -> &'a str {
+  str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default()
 }
 
 // An enum to represent the different results of conditionals
@@ -49,26 +202,29 @@ pub enum CondResult {
 
 impl Conditional {
   /// Given a line, evaluate the condition on it
-  pub fn evaluate<'a, T: 'a>(&self, line: &'a T) -> CondResult
+  pub fn evaluate<'a, T>(&self, line: &'a T) -> CondResult
   where
-    T: AsRef<str>,
+    T: 'a + AsRef<[u8]>,
   {
     use self::CondResult::*;
 
+    let line = line.as_ref();
+
     match *self {
       Conditional::RelChar(idx, c) => {
         let idx = idx as usize;
-        Bool(line.as_ref().get(idx..idx + 1) == Some(&c.to_string()))
+        let mut buf = [0; 4];
+        let c = c.encode_utf8(&mut buf).as_bytes();
+        Bool(line.get(idx..idx + c.len()) == Some(c))
       }
       Conditional::Int(ref r, b) => {
-        let lineref = line.as_ref();
-        let linelen = lineref.len();
+        let linelen = line.len();
         let lower = r.start;
         let upper = r.end;
         let new_upper = cmp::min(linelen, upper);
         let range = lower..new_upper;
 
-        let cell = lineref.get(range);
+        let cell = line.get(range).and_then(|s| str::from_utf8(s).ok());
 
         match cell {
           None => Bool(false),
@@ -78,32 +234,49 @@ impl Conditional {
           },
         }
       }
-      Conditional::Number(ref r) => {
-        let s = line.as_ref();
-        let l = s.len();
-        let lower = r.start;
-        let upper = r.end;
-        let new_upper = cmp::min(l, upper);
-        let range = lower..new_upper;
-
-        let cell = s.get(range);
-
-        match cell {
-          None => Number(None),
-          Some(s) => match s.trim().parse::<usize>() {
-              Ok(x) => Number(Some(x)),
-              _ => Number(None),
-            }
-          }
-        }
+      Conditional::Number(ref r) => Number(read_number(line, r)),
+      Conditional::Count(ref r, per_line) => {
+        let per_line = cmp::max(per_line, 1) as usize;
+        Number(read_number(line, r).map(|n| n.div_ceil(per_line)))
+      }
+      Conditional::Char(idx) => {
+        Number(line.get(idx as usize..).and_then(first_char))
       }
     }
   }
+}
+
+/// Read a non-negative integer from the given cell of a line
+fn read_number(line: &[u8], r: &Range<usize>) -> Option<usize> {
+  let upper = cmp::min(line.len(), r.end);
+
+  line
+    .get(r.start..upper)
+    .and_then(|s| str::from_utf8(s).ok())
+    .and_then(|s| s.trim().parse::<usize>().ok())
+}
+
+/// The code point of the first char of `bytes`. If they do not start with
+/// valid UTF-8, the first byte is read as Latin-1.
+fn first_char(bytes: &[u8]) -> Option<usize> {
+  let start = &bytes[..cmp::min(bytes.len(), 4)];
+  let valid = match str::from_utf8(start) {
+    Ok(s) => s,
+    Err(e) => valid_start(start, &e),
+  };
+
+  match valid.chars().next() {
+    Some(c) => Some(c as usize),
+    None => bytes.first().map(|&b| b as usize),
+  }
+}
 
 #[cfg(test)]
 mod tests {
+  use card::cell::Cell::*;
+  use card::cell::Value;
   use card::line::CondResult::*;
-  use card::line::Conditional;
+  use card::line::{Conditional, Line};
 
   #[test]
   fn relchar_can_be_evaluated() {
@@ -123,4 +296,62 @@ mod tests {
     assert_eq!(Bool(false), cond1.evaluate(&line));
   }
 
+  #[test]
+  fn count_gives_number_of_lines() {
+    let line = "FUNCT /        1       5";
+
+    assert_eq!(Number(Some(3)), Conditional::Count(16..24, 2).evaluate(&line));
+    assert_eq!(Number(Some(5)), Conditional::Count(16..24, 1).evaluate(&line));
+    assert_eq!(Number(None), Conditional::Count(24..32, 2).evaluate(&line));
+  }
+
+  #[test]
+  fn char_gives_code_point() {
+    let line = "DISPL /A";
+    let cond = Conditional::Char(7);
+
+    assert_eq!(Number(Some('A' as usize)), cond.evaluate(&line));
+    assert_eq!(Number(None), Conditional::Char(8).evaluate(&line));
+
+    let latin1 = b"DISPL /\xe4";
+    assert_eq!(Number(Some('\u{e4}' as usize)), cond.evaluate(&latin1));
+  }
+
+  const NODELINE: Line = Line::Cells(&[Kw, Integer(8), Float(16), Float(16)]);
+
+  #[test]
+  fn line_can_be_parsed() {
+    let line = "NODE  /       28     30.29999924";
+    let parsed = NODELINE.parse(&line);
+    let values: Vec<&Value> = parsed.iter().map(|c| &c.value).collect();
+
+    assert_eq!(
+      vec![
+        &Value::Keyword("NODE  /"),
+        &Value::Integer(28),
+        &Value::Float(30.29999924),
+        &Value::Blank,
+      ],
+      values
+    );
+    assert_eq!(16..32, parsed[2].range);
+    assert_eq!(32..48, parsed[3].range);
+  }
+
+  #[test]
+  fn ges_has_no_cells() {
+    use card::ges::GesType;
+
+    let line = "        PART 1234";
+    assert!(Line::Ges(GesType::GesNode).parse(&line).is_empty());
+  }
+
+  #[test]
+  fn non_utf8_cells_are_invalid() {
+    let line = b"NODE  /       28    30.\xe429999924".to_vec();
+    let parsed = NODELINE.parse(&line);
+
+    assert_eq!(Value::Integer(28), parsed[1].value);
+    assert_eq!(Value::Invalid("    30."), parsed[2].value);
+  }
 }
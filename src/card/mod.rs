@@ -11,25 +11,128 @@
 //! [`carddata`](::carddata) module.
 //!
 pub mod cell;
+pub mod field;
 pub mod ges;
 pub mod keyword;
 pub mod line;
 
+use std::ptr;
+
+use self::field::Field;
 use self::keyword::Keyword;
-use self::line::Line;
+use self::line::{Line, ParsedLine};
 use carddata::*;
+use nocommentiter::CommentLess;
+use schema;
+use skipresult::SkipResult;
 
 /// A card consists of severals [`Line`](::card::line::Line)s, and starts with a
 /// given [`Keyword`](::card::keyword::Keyword). If `ownfold` is true, than each
 /// card of this type will get an own fold. Otherwise, all adjacent cards of
 /// that types are gathered into one fold.
+///
+/// `fields` holds the [`Field`](::card::field::Field)s for the cells of each
+/// line, in the same order as `lines`. It may be shorter than `lines`, and an
+/// entry may be empty, if there is no metadata for a line.
 #[derive(Debug)]
 pub struct Card {
   pub lines: &'static [Line],
+  pub fields: &'static [&'static [Field]],
   pub ownfold: bool,
   pub keyword: Keyword,
 }
 
+impl Card {
+  /// The [`Field`](::card::field::Field)s of the cells of `line`, which needs
+  /// to be one of the `lines` of this card, or of one of its
+  /// [`Case`](::card::line::Case)s. Empty if there is no metadata.
+  pub fn fields(&self, line: &Line) -> &'static [Field] {
+    find_fields(self.lines, self.fields, line).unwrap_or(&[])
+  }
+
+  /// The [`Field`](::card::field::Field) of the cell with index `cell` of
+  /// `line`, see [`fields`](::card::Card::fields).
+  pub fn field(&self, line: &Line, cell: usize) -> Option<&'static Field> {
+    self.fields(line).get(cell)
+  }
+
+  /// Parse the lines of a card into their
+  /// [`ParsedCell`](::card::line::ParsedCell)s. `lines` needs to start with
+  /// the line containing the keyword. Comments are skipped, and parsing stops
+  /// where the card ends.
+  pub fn parse<'a, T>(&self, lines: &'a [T]) -> Vec<ParsedLine<'a>>
+  where
+    T: AsRef<[u8]>,
+  {
+    let mut li = lines.iter().enumerate().remove_comments();
+    let first = SkipResult {
+      nextline: li.next(),
+      nextline_kw: Some(self.keyword),
+      skip_end: None,
+    };
+    match first.nextline {
+      None => vec![],
+      Some(_) => li.parse_card_as(self, &first).1,
+    }
+  }
+}
+
+/// Find the fields of `line` in `lines`, descending into the cases of a
+/// [`Switch`](::card::line::Line::Switch) or a
+/// [`Select`](::card::line::Line::Select), or into a repeated
+/// [`LineGroup`](::card::line::LineGroup).
+fn find_fields(
+  lines: &'static [Line],
+  fields: &'static [&'static [Field]],
+  line: &Line,
+) -> Option<&'static [Field]> {
+  for (i, l) in lines.iter().enumerate() {
+    if ptr::eq(l, line) {
+      return Some(fields.get(i).cloned().unwrap_or(&[]));
+    }
+    if let Line::Switch(_, cases) | Line::Select(_, cases) = *l {
+      let found = cases
+        .iter()
+        .filter_map(|c| find_fields(c.lines, c.fields, line))
+        .next();
+      if found.is_some() {
+        return found;
+      }
+    }
+    if let Line::RepeatGroup(ref g, _) | Line::RepeatUntil(ref g, _) = *l {
+      let found = find_fields(g.lines, g.fields, line);
+      if found.is_some() {
+        return found;
+      }
+    }
+  }
+  None
+}
+
+/// The layouts of cards in older Pamcrash releases. An entry `(kw, version,
+/// card)` means that `card` is the layout of `kw` in decks with an
+/// `INPUTVERSION` below `version`. The entries of a keyword are sorted by
+/// ascending version. Where a layout comes from, or that its source is
+/// unknown, is documented at its card.
+static OLD_LAYOUTS: &[(Keyword, u32, &Card)] =
+  &[(Keyword::Thnod, 2010, &THNOD_2009)];
+
+impl Card {
+  /// The layout of the card for `kw` in a deck with the given
+  /// `INPUTVERSION`. If the version is unknown, the current layout is
+  /// returned, like the conversion `From<&Keyword>` does.
+  pub fn for_version(kw: &Keyword, version: Option<u32>) -> &'static Card {
+    let old = version.and_then(|v| {
+      OLD_LAYOUTS
+        .iter()
+        .find(|(k, until, _)| k == kw && v < *until)
+        .map(|(_, _, card)| *card)
+    });
+
+    old.unwrap_or_else(|| kw.into())
+  }
+}
+
 impl<'a> From<&'a Keyword> for &'static Card {
   fn from(kw: &'a Keyword) -> &'static Card {
     match *kw {
@@ -95,6 +198,141 @@ impl<'a> From<&'a Keyword> for &'static Card {
       Keyword::PartLlink => &PARTLLINK,
       Keyword::PartPlink => &PARTPLINK,
       Keyword::PartGap => &PARTGAP,
+      // Material
+      Keyword::Mater => &MATER,
+      // Boundary conditions
+      Keyword::Bounc => &BOUNC,
+      Keyword::Displ => &DISPL,
+      Keyword::Invel => &INVEL,
+      // Constraints
+      Keyword::Rbody => &RBODY,
+      // Groups
+      Keyword::Group => &GROUP,
+      // Contact
+      Keyword::Cntac => &CNTAC,
+      // Functions
+      Keyword::Funct => &FUNCT,
+      // Frames
+      Keyword::Frame => &FRAME,
+      // Output
+      Keyword::Thnod => &THNOD,
+      Keyword::Thloc => &THLOC,
+      Keyword::Secfo => &SECFO,
+      // Control
+      Keyword::Inputversion => &INPUTVERSION,
+      Keyword::Analysis => &ANALYSIS,
+      Keyword::Solver => &SOLVER,
+      Keyword::Unit => &UNIT,
+      Keyword::Signal => &SIGNAL,
+      Keyword::Title => &TITLE,
+      Keyword::Inclu => &INCLU,
+      Keyword::Runend => &RUNEND,
+      Keyword::Octrl => &OCTRL,
+      Keyword::Ctrl => &CTRL,
+      // Custom
+      Keyword::Custom(i) => schema::card(i),
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use card::field::Field;
+  use card::keyword::Keyword;
+  use card::line::Line;
+  use card::Card;
+  use carddata::*;
+
+  const CARDS: [&Card; 79] = [
+    &NODE, &CNODE, &MASS, &NSMAS, &NSMAS2, &SOLID, &HEXA20, &PENT15, &PENTA6,
+    &TETR10, &TETR4, &BSHEL, &TSHEL, &SHELL, &SHEL6, &SHEL8, &MEMBR, &BEAM,
+    &SPRGBM, &BAR, &SPRING, &JOINT, &KJOIN, &MTOJNT, &SPHEL, &SPHELO, &GAP,
+    &IMPMA, &ELINK, &LLINK, &SLINK, &PLINK, &TIED, &PARTSOLID, &PARTBSHEL,
+    &PARTTETRA, &PARTSPHEL, &PARTCOS3D, &PARTTSHEL, &PARTSHELL, &PARTMEMBR,
+    &PARTBAR, &PARTBEAM, &PARTSPRING, &PARTSPRGBM, &PARTMBSPR, &PARTJOINT,
+    &PARTKJOIN, &PARTMBKJN, &PARTMTOJNT, &PARTTIED, &PARTSLINK, &PARTELINK,
+    &PARTLLINK, &PARTPLINK, &PARTGAP, &MATER, &BOUNC, &DISPL, &INVEL, &RBODY,
+    &GROUP, &CNTAC, &FUNCT, &FRAME, &THNOD, &THLOC, &SECFO, &INPUTVERSION,
+    &ANALYSIS, &SOLVER, &UNIT, &SIGNAL, &TITLE, &INCLU, &RUNEND, &OCTRL, &CTRL,
+    &THNOD_2009,
+  ];
+
+  fn check_fields(lines: &[Line], fields: &[&[Field]], kw: Keyword) {
+    assert!(fields.len() <= lines.len(), "{:?}", kw);
+
+    for (line, f) in lines.iter().zip(fields.iter()) {
+      assert!(
+        f.is_empty() || f.len() == line.cells().len(),
+        "{:?}: {:?}",
+        kw,
+        line
+      );
+    }
+
+    for line in lines {
+      if let Line::Switch(_, cases) | Line::Select(_, cases) = *line {
+        for c in cases {
+          check_fields(c.lines, c.fields, kw);
+        }
+      }
+      if let Line::RepeatGroup(ref g, _) | Line::RepeatUntil(ref g, _) = *line
+      {
+        check_fields(g.lines, g.fields, kw);
+      }
+    }
+  }
+
+  #[test]
+  fn fields_match_cells() {
+    for card in CARDS.iter() {
+      check_fields(card.lines, card.fields, card.keyword);
+      assert!(
+        card.fields.first().is_some_and(|f| !f.is_empty()),
+        "{:?} has no fields",
+        card.keyword
+      );
+    }
+  }
+
+  #[test]
+  fn layout_depends_on_version() {
+    use std::ptr;
+
+    let kw = Keyword::Thnod;
+
+    assert!(ptr::eq(&THNOD, Card::for_version(&kw, None)));
+    assert!(ptr::eq(&THNOD, Card::for_version(&kw, Some(2010))));
+    assert!(ptr::eq(&THNOD_2009, Card::for_version(&kw, Some(2009))));
+    assert!(ptr::eq(&NODE, Card::for_version(&Keyword::Node, Some(2009))));
+  }
+
+  const PARTSHELL_LINES: [&str; 8] = [
+    "PART  /        1   SHELL       2       0       0       0",
+    "NAME PART_1",
+    "$#  DTELIM    TSCALF   DTRATIO",
+    "        0.        0.",
+    "        0.        0.        0.",
+    "        1.    3       0.    0",
+    "    0        0.        1.        0.        0.",
+    "END_PART",
+  ];
+
+  #[test]
+  fn fields_can_be_looked_up() {
+    let parsed = PARTSHELL.parse(&PARTSHELL_LINES);
+    let names: Vec<&str> = parsed
+      .iter()
+      .map(|p| PARTSHELL.field(p.line, 1).map(|f| f.name).unwrap_or(""))
+      .collect();
+
+    assert_eq!(
+      vec!["IDPRT", "TITLE", "TSCALF", "EPSINI", "NINT", "BLANK", ""],
+      names
+    );
+
+    let x = NODE.field(&NODE.lines[0], 2).unwrap();
+    assert_eq!(("X", Some("length")), (x.name, x.unit));
+    assert!(NODE.field(&NODE.lines[0], 5).is_none());
+    assert!(NODE.fields(&MASS.lines[0]).is_empty());
+  }
+}
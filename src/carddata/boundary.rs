@@ -0,0 +1,170 @@
+//! This modules holds the the global static boundary and initial condition
+//! [`Card`](::card::Card) instances. The nodes of these cards are given either
+//! by the node ID on the keyword line, or by a GES if it is 0.
+use card::cell::Cell::*;
+use card::field::*;
+use card::ges::GesType::*;
+use card::keyword::Keyword::*;
+use card::line::Line::*;
+use card::Card;
+
+const IDNOD: Field =
+  Field::new("IDNOD", "Node ID, nodes given by the GES if 0").default("0");
+const ISENS: Field = Field::new("ISENS", "Sensor ID").default("0");
+
+pub static BOUNC: Card = Card {
+  lines: &[
+    Cells(&[Kw, Integer(8), Binary(8), Integer(8), Integer(8)]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDNOD,
+      Field::new("XYZUVW", "Fixed degrees of freedom"),
+      IFRA,
+      ISENS,
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Bounc,
+};
+
+pub static DISPL: Card = Card {
+  lines: &[
+    Cells(&[
+      Kw,
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Binary(8),
+      Integer(8),
+      Integer(8),
+    ]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDNOD,
+      Field::new("IDFUN", "Function ID of the imposed motion"),
+      Field::new("ITYP", "Imposed displacement, velocity or acceleration")
+        .default("0"),
+      Field::new("XYZUVW", "Imposed degrees of freedom"),
+      IFRA,
+      ISENS,
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Displ,
+};
+
+pub static INVEL: Card = Card {
+  lines: &[
+    Cells(&[
+      Kw,
+      Integer(8),
+      Float(8),
+      Float(8),
+      Float(8),
+      Float(8),
+      Float(8),
+      Float(8),
+      Integer(8),
+      Integer(8),
+    ]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDNOD,
+      Field::new("VELX0", "Initial velocity in x").unit("length/time"),
+      Field::new("VELY0", "Initial velocity in y").unit("length/time"),
+      Field::new("VELZ0", "Initial velocity in z").unit("length/time"),
+      Field::new("VANX0", "Initial angular velocity around x").unit("1/time"),
+      Field::new("VANY0", "Initial angular velocity around y").unit("1/time"),
+      Field::new("VANZ0", "Initial angular velocity around z").unit("1/time"),
+      IFRA,
+      Field::new("IRIGB", "Rigid body velocity flag").default("0"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Invel,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_BOUNC: [&str; 24] = [
+    "$#         IDNOD  XYZUVW    IFRA   ISENS",
+    "BOUNC /     1000  110111       0       0",
+    "$#                                                                         TITLE",
+    "NAME Impactor_COG                                                               ",
+    "$#         IDNOD  XYZUVW    IFRA   ISENS",
+    "BOUNC /        0  111111       0       0",
+    "$#                                                                         TITLE",
+    "NAME Fixed End                                                                  ",
+    "        NOD        1       10       19       28       37       46",
+    "        NOD       55       64       73       82       91      100",
+    "        END",
+    "$#         IDNOD   IDFUN    ITYP  XYZUVW    IFRA   ISENS",
+    "DISPL /        0       3       1  001000       0       0",
+    "NAME Pushed End",
+    "        NOD        1:9      118:131      326:334",
+    "        END",
+    "$#         IDNOD   VELX0   VELY0   VELZ0   VANX0   VANY0   VANZ0    IFRA   IRIGB",
+    "INVEL /     1000      0.      0.    -7.8      0.      0.      0.       0       0",
+    "$#                                                                         TITLE",
+    "NAME InitVeloc_impactor                                                         ",
+    "INVEL /     1001      0.      0.    -7.8      0.      0.      0.       0       0",
+    "NAME InitVeloc_impactor                                                         ",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_bounc_invel() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![
+      (1, 3, Bounc),
+      (5, 10, Bounc),
+      (12, 15, Displ),
+      (17, 19, Invel),
+      (20, 21, Invel),
+    ];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_BOUNC);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_bounc() {
+    use card::cell::Value;
+    use carddata::{BOUNC, INVEL};
+
+    let parsed = BOUNC.parse(&CARD_BOUNC[5..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 5).collect();
+
+    assert_eq!(vec![5, 7, 8, 9, 10], v);
+    assert_eq!(Value::Binary("111111"), parsed[0].cells[2].value);
+    assert_eq!(
+      Some("XYZUVW"),
+      BOUNC.field(parsed[0].line, 2).map(|f| f.name)
+    );
+
+    let parsed = INVEL.parse(&CARD_BOUNC[17..]);
+    assert_eq!(Value::Float(-7.8), parsed[0].cells[4].value);
+    assert_eq!(2, parsed.len());
+  }
+}
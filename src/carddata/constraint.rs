@@ -0,0 +1,252 @@
+//! This modules holds the the global static constraint [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::ges::GesType::*;
+use card::keyword::Keyword::*;
+use card::line::Case;
+use card::line::Conditional::*;
+use card::line::Line::*;
+use card::Card;
+
+const MRB: Field = Field::new("Mrb", "Mass of the rigid body").unit("mass");
+
+/// A rigid body. The type ITRB decides which line follows the title line,
+/// the standard type 0 has none.
+pub static RBODY: Card = Card {
+  lines: &[
+    Provides(
+      &[
+        Kw,
+        Integer(8),
+        Blank(8),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+        Float(8),
+      ],
+      Number(24..32),
+    ),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Switch(
+      0,
+      &[
+        // Rigid body with constrained degrees of freedom
+        Case {
+          value: 1,
+          lines: &[Cells(&[
+            Integer(8),
+            Integer(8),
+            Integer(8),
+            Integer(8),
+            Integer(8),
+            Integer(8),
+            Blank(16),
+          ])],
+          fields: &[&[
+            Field::new("ITX", "Translation in x fixed if 1").default("0"),
+            Field::new("ITY", "Translation in y fixed if 1").default("0"),
+            Field::new("ITZ", "Translation in z fixed if 1").default("0"),
+            Field::new("IRX", "Rotation around x fixed if 1").default("0"),
+            Field::new("IRY", "Rotation around y fixed if 1").default("0"),
+            Field::new("IRZ", "Rotation around z fixed if 1").default("0"),
+            BLANK,
+          ]],
+        },
+        // Rigid body with failure
+        Case {
+          value: 2,
+          lines: &[Cells(&[Float(8), Float(8), Float(8), Blank(40)])],
+          fields: &[&[
+            Field::new("FNmax", "Normal force at failure").unit("force"),
+            Field::new("FTmax", "Tangential force at failure").unit("force"),
+            Field::new("Tfail", "Time of failure").unit("time"),
+            BLANK,
+          ]],
+        },
+        // Rigid body with given mass and inertia
+        Case {
+          value: 3,
+          lines: &[Cells(&[
+            Integer(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Blank(24),
+          ])],
+          fields: &[&[
+            Field::new("IT3FL", "Inertia flag").default("0"),
+            MRB,
+            Field::new("I1", "Principal moment of inertia 1")
+              .unit("mass*length^2"),
+            Field::new("I2", "Principal moment of inertia 2")
+              .unit("mass*length^2"),
+            Field::new("I3", "Principal moment of inertia 3")
+              .unit("mass*length^2"),
+            BLANK,
+          ]],
+        },
+        // Rigid body with given mass and full inertia tensor
+        Case {
+          value: 4,
+          lines: &[Cells(&[
+            Float(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Float(8),
+            Blank(8),
+          ])],
+          fields: &[&[
+            MRB,
+            Field::new("Ixx", "Inertia tensor xx").unit("mass*length^2"),
+            Field::new("Iyy", "Inertia tensor yy").unit("mass*length^2"),
+            Field::new("Izz", "Inertia tensor zz").unit("mass*length^2"),
+            Field::new("Ixy", "Inertia tensor xy").unit("mass*length^2"),
+            Field::new("Iyz", "Inertia tensor yz").unit("mass*length^2"),
+            Field::new("Izx", "Inertia tensor zx").unit("mass*length^2"),
+            BLANK,
+          ]],
+        },
+      ],
+    ),
+    Ges(GesNode),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDRB", "Rigid body ID"),
+      BLANK,
+      Field::new("ITRB", "Rigid body type").default("0"),
+      Field::new("IDNODcog", "Node ID of the center of gravity").default("0"),
+      Field::new("IDNOD1", "Node ID of the local frame origin").default("0"),
+      Field::new("IDNOD2", "Node ID of the local frame axis").default("0"),
+      Field::new("ISENS", "Sensor ID").default("0"),
+      IFRA,
+      Field::new("HRB", "Rigid body length scale").unit("length"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Rbody,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_RBODY: [&str; 20] = [
+    "$#          IDRB   BLANK    ITRBIDNODcog  IDNOD1  IDNOD2   ISENS    IFRA     HRB",
+    "RBODY /        1               3    1000     671     691       0       0        ",
+    "$#                                                                         TITLE",
+    "NAME Impactor                                                                   ",
+    "$# IT3FL     Mrb      I1      I2      I3   BLANK   BLANK   BLANK",
+    "       0    100.   0.001   0.001   0.001                        ",
+    "        PART>NOD        2",
+    "        END",
+    "$#          IDRB   BLANK    ITRBIDNODcog  IDNOD1  IDNOD2   ISENS    IFRA     HRB",
+    "RBODY /        2               0       0       0       0       0       0        ",
+    "NAME Spider",
+    "        NOD        1       10       19       28       37       46",
+    "        NOD       55       64       73       82       91      100",
+    "        END",
+    "RBODY /        3               0       0       0       0       0       0        ",
+    "NAME Spider",
+    "        NOD        1       10       19       28       37       46",
+    "        END",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  const CARD_RBODY_TYPES: [&str; 15] = [
+    "RBODY /        4               1       0       0       0       0       0        ",
+    "NAME Fixed",
+    "$#   ITX     ITY     ITZ     IRX     IRY     IRZ",
+    "       1       1       1       0       0       0",
+    "        NOD        1        2",
+    "        END",
+    "RBODY /        5               2       0       0       0       0       0        ",
+    "NAME Failing",
+    "     10.      5.     0.1",
+    "        NOD        3        4",
+    "        END",
+    "RBODY /        6               4    1000       0       0       0       0        ",
+    "NAME Tensor",
+    "    100.      1.      2.      3.     0.1     0.2     0.3",
+    "        END",
+  ];
+
+  #[test]
+  fn fold_rbody() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> =
+      vec![(1, 7, Rbody), (9, 13, Rbody), (14, 17, Rbody)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_RBODY);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_rbody() {
+    use card::cell::Value;
+    use carddata::RBODY;
+
+    let parsed = RBODY.parse(&CARD_RBODY[1..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 3, 5, 6, 7], v);
+    assert_eq!(Value::Integer(1000), parsed[0].cells[4].value);
+    assert_eq!(Value::Float(100.), parsed[2].cells[1].value);
+    assert_eq!(Some("Mrb"), RBODY.field(parsed[2].line, 1).map(|f| f.name));
+
+    let parsed = RBODY.parse(&CARD_RBODY[9..]);
+    assert_eq!(5, parsed.len());
+  }
+
+  #[test]
+  fn fold_rbody_types() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> =
+      vec![(0, 5, Rbody), (6, 10, Rbody), (11, 14, Rbody)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_RBODY_TYPES);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_rbody_types() {
+    use card::cell::Value;
+    use carddata::RBODY;
+
+    let parsed = RBODY.parse(&CARD_RBODY_TYPES[..6]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index).collect();
+    assert_eq!(vec![0, 1, 3, 4, 5], v);
+    assert_eq!(Value::Integer(1), parsed[2].cells[2].value);
+    assert_eq!(Some("ITZ"), RBODY.field(parsed[2].line, 2).map(|f| f.name));
+
+    let parsed = RBODY.parse(&CARD_RBODY_TYPES[6..11]);
+    assert_eq!(Value::Float(5.), parsed[2].cells[1].value);
+    assert_eq!(
+      Some("FTmax"),
+      RBODY.field(parsed[2].line, 1).map(|f| f.name)
+    );
+
+    let parsed = RBODY.parse(&CARD_RBODY_TYPES[11..]);
+    assert_eq!(4, parsed.len());
+    assert_eq!(Value::Float(0.3), parsed[2].cells[6].value);
+    assert_eq!(Some("Izx"), RBODY.field(parsed[2].line, 6).map(|f| f.name));
+  }
+}
@@ -0,0 +1,259 @@
+//! This modules holds the the global static contact [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::ges::GesType::*;
+use card::keyword::Keyword::*;
+use card::line::Conditional::*;
+use card::line::Line::*;
+use card::line::{Case, Line};
+use card::Card;
+
+const THICKNESS: Line = Cells(&[
+  Float(10),
+  Float(10),
+  Integer(10),
+  Float(10),
+  Blank(20),
+  Integer(10),
+]);
+const THICKNESS_FIELDS: &[Field] = &[
+  Field::new("T1SL", "Slave contact thickness").unit("length"),
+  Field::new("T2SL", "Master contact thickness").unit("length"),
+  Field::new("ISENS", "Sensor ID").default("0"),
+  Field::new("Hcont", "Contact thickness scale factor"),
+  BLANK,
+  Field::new("IEDGE", "Edge treatment flag").default("0"),
+];
+
+const PENALTY: Line = Cells(&[
+  Integer(5),
+  Float(10),
+  Float(10),
+  Integer(5),
+  Float(10),
+  Blank(15),
+  Float(10),
+  Blank(5),
+  Float(10),
+]);
+const PENALTY_FIELDS: &[Field] = &[
+  Field::new("PCP", "Penalty computation flag"),
+  Field::new("SLFACM", "Scale factor for the contact stiffness"),
+  Field::new("FSVNL", "Nonlinear penalty stiffness scale factor"),
+  Field::new("IKFOR", "Penalty force flag"),
+  Field::new("PENKIN", "Kinematic penalty factor"),
+  BLANK,
+  Field::new("CTFRC", "Contact force cutoff").unit("force"),
+  BLANK,
+  Field::new("TLSTIF", "Time of stiffness increase").unit("time"),
+];
+
+const FRICTION: Line =
+  Cells(&[Float(10), Integer(10), Float(10), Blank(10), Integer(10)]);
+const FRICTION_FIELDS: &[Field] = &[
+  Field::new("FRICT", "Coulomb friction coefficient"),
+  Field::new("IDFRIC", "Friction model ID").default("0"),
+  Field::new("XDMP1", "Damping coefficient"),
+  BLANK,
+  Field::new("ICOUFR", "Coupled friction flag").default("0"),
+];
+
+const SEPARATION: Line = Cells(&[Blank(10), Float(10), Float(10)]);
+const SEPARATION_FIELDS: &[Field] = &[
+  BLANK,
+  Field::new("SEPSTR", "Separation stress").unit("force/length^2"),
+  Field::new("SEPTHK", "Separation thickness").unit("length"),
+];
+
+const PRESSURE: Line = Cells(&[Blank(75), Integer(5)]);
+const PRESSURE_FIELDS: &[Field] =
+  &[BLANK, Field::new("IPRES", "Contact pressure output flag")];
+
+const RMVI: Field = Field::new("RMVI", "Remove initial penetrations flag");
+const ERODI: Field = Field::new("ERODI", "Erosion flag");
+const LEAKI: Field = Field::new("LEAKI", "Leakage flag");
+const AC32: Field = Field::new("AC32", "Accuracy flag");
+const IFRED: Field = Field::new("IFRED", "Frequency of the contact search");
+const DTHKPLK: Field =
+  Field::new("DTHKPLK", "Thickness for plink contacts").unit("length");
+const ADJTOL: Field =
+  Field::new("ADJTOL", "Tolerance for adjacent segments").unit("length");
+
+pub static CNTAC: Card = Card {
+  lines: &[
+    Provides(&[Kw, Integer(8), Integer(8)], Number(16..24)),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Switch(
+      0,
+      &[
+        // Node to segment with edge treatment
+        Case {
+          value: 33,
+          lines: &[
+            THICKNESS,
+            PENALTY,
+            FRICTION,
+            Cells(&[
+              Integer(5),
+              Integer(5),
+              Integer(5),
+              Integer(5),
+              Blank(15),
+              Integer(5),
+              Float(10),
+              Float(10),
+            ]),
+            SEPARATION,
+            PRESSURE,
+            Ges(GesNode),
+            Ges(GesFace),
+          ],
+          fields: &[
+            THICKNESS_FIELDS,
+            PENALTY_FIELDS,
+            FRICTION_FIELDS,
+            &[RMVI, ERODI, LEAKI, AC32, BLANK, IFRED, DTHKPLK, ADJTOL],
+            SEPARATION_FIELDS,
+            PRESSURE_FIELDS,
+          ],
+        },
+        // Self contact
+        Case {
+          value: 36,
+          lines: &[
+            THICKNESS,
+            PENALTY,
+            FRICTION,
+            Cells(&[
+              Integer(5),
+              Integer(5),
+              Integer(5),
+              Integer(5),
+              Blank(10),
+              Integer(5),
+              Integer(5),
+              Float(10),
+              Float(10),
+            ]),
+            SEPARATION,
+            PRESSURE,
+            Ges(GesFace),
+          ],
+          fields: &[
+            THICKNESS_FIELDS,
+            PENALTY_FIELDS,
+            FRICTION_FIELDS,
+            &[
+              RMVI,
+              ERODI,
+              LEAKI,
+              AC32,
+              BLANK,
+              Field::new("IOMIT", "Omit contacts of adjacent segments"),
+              IFRED,
+              DTHKPLK,
+              ADJTOL,
+            ],
+            SEPARATION_FIELDS,
+            PRESSURE_FIELDS,
+          ],
+        },
+      ],
+    ),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDCTC", "Contact ID"),
+      Field::new("NTYPE", "Contact type"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Cntac,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_CNTAC: [&str; 39] = [
+    "$#         IDCTC   NTYPE",
+    "CNTAC /        1      33",
+    "$#                                                                         TITLE",
+    "NAME Contact: Box to wall                                                       ",
+    "$#    T1SL      T2SL     ISENS     Hcont                         IEDGE",
+    "        0.        0.         0        1.                             0",
+    "$#PCP    SLFACM     FSVNLIKFOR    PENKIN                    CTFRC         TLSTIF",
+    "            0.1        0.    0        0.                                        ",
+    "$#   FRICT    IDFRIC     XDMP1              ICOUFR",
+    "       0.1         0       0.1                    ",
+    "$#RMVIERODILEAKIAC32               IFRED   DTHKPLK    ADJTOL",
+    "    0         0    1                                        ",
+    "$#   BLANK    SEPSTR    SEPTHK",
+    "                              ",
+    "$#                                                                    BLANKIPRES",
+    "                                                                                ",
+    "        PART        1",
+    "        END",
+    "        PART        2",
+    "        END",
+    "$#         IDCTC   NTYPE",
+    "CNTAC /        2      36",
+    "$#                                                                         TITLE",
+    "NAME Contact: Self for hat section                                              ",
+    "$#    T1SL      T2SL     ISENS     Hcont                         IEDGE",
+    "        0.        0.         0       0.9                             0",
+    "$#PCP    SLFACM     FSVNLIKFOR    PENKIN                    CTFRC         TLSTIF",
+    "            0.1        0.    0                                                  ",
+    "$#   FRICT    IDFRIC     XDMP1              ICOUFR",
+    "       0.1         0       0.1                    ",
+    "$#RMVIERODILEAKIAC32          IOMITIFRED   DTHKPLK    ADJTOL",
+    "    0         0    1                                        ",
+    "",
+    "$#                                                                    BLANKIPRES",
+    "                                                                                ",
+    "        PART        1",
+    "        END",
+    "$#          IDEL   IDPRT  IDNOD1  IDNOD2  IDNOD3  IDNOD4",
+    "SHELL /     3129       1       1    2967    2971    2970",
+  ];
+
+  #[test]
+  fn fold_cntac() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![(1, 19, Cntac), (21, 36, Cntac)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_CNTAC);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_cntac() {
+    use card::cell::Value;
+    use carddata::CNTAC;
+
+    let parsed = CNTAC.parse(&CARD_CNTAC[1..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 3, 5, 7, 9, 11, 13, 15, 16, 17, 18, 19], v);
+    assert_eq!(Value::Integer(1), parsed[5].cells[3].value);
+    assert_eq!(
+      Some("IFRED"),
+      CNTAC.field(parsed[5].line, 5).map(|f| f.name)
+    );
+
+    let parsed = CNTAC.parse(&CARD_CNTAC[21..]);
+    assert_eq!(Value::Integer(36), parsed[0].cells[2].value);
+    assert_eq!(Value::Float(0.9), parsed[2].cells[3].value);
+    assert_eq!(
+      Some("IOMIT"),
+      CNTAC.field(parsed[5].line, 5).map(|f| f.name)
+    );
+    assert_eq!(10, parsed.len());
+  }
+}
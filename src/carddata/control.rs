@@ -0,0 +1,157 @@
+//! This modules holds the the global static control [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::keyword::Keyword::*;
+use card::line::Line::*;
+use card::Card;
+
+pub static INPUTVERSION: Card = Card {
+  lines: &[Cells(&[Fixed("INPUTVERSION"), Integer(5)])],
+  fields: &[&[KEYWORD, Field::new("VERSION", "Version of the input format")]],
+  ownfold: true,
+  keyword: Inputversion,
+};
+
+pub static ANALYSIS: Card = Card {
+  lines: &[Cells(&[Kw, Str(72)])],
+  fields: &[&[KEYWORD, Field::new("TYPE", "Type of the analysis")]],
+  ownfold: true,
+  keyword: Analysis,
+};
+
+pub static SOLVER: Card = Card {
+  lines: &[Cells(&[Kw, Str(72)])],
+  fields: &[&[KEYWORD, Field::new("SOLVER", "Solver to use")]],
+  ownfold: true,
+  keyword: Solver,
+};
+
+pub static UNIT: Card = Card {
+  lines: &[Cells(&[Fixed("UNIT"), Str(9), Str(9), Str(9), Str(9)])],
+  fields: &[&[
+    KEYWORD,
+    Field::new("LENGTH", "Unit of length"),
+    Field::new("MASS", "Unit of mass"),
+    Field::new("TIME", "Unit of time"),
+    Field::new("TEMP", "Unit of temperature"),
+  ]],
+  ownfold: true,
+  keyword: Unit,
+};
+
+pub static SIGNAL: Card = Card {
+  lines: &[Cells(&[Kw, Str(72)])],
+  fields: &[&[KEYWORD, Field::new("SIGNAL", "Signal handling")]],
+  ownfold: true,
+  keyword: Signal,
+};
+
+pub static TITLE: Card = Card {
+  lines: &[Cells(&[Kw, Str(72)])],
+  fields: &[&[KEYWORD, Field::new("TITLE", "Title of the model")]],
+  ownfold: true,
+  keyword: Title,
+};
+
+pub static INCLU: Card = Card {
+  lines: &[Cells(&[Kw, Str(72)])],
+  fields: &[&[KEYWORD, Field::new("FILE", "Path of the included file")]],
+  ownfold: true,
+  keyword: Inclu,
+};
+
+pub static RUNEND: Card = Card {
+  lines: &[Cells(&[Kw]), Block("END_RUNEND")],
+  fields: &[&[KEYWORD]],
+  ownfold: true,
+  keyword: Runend,
+};
+
+pub static OCTRL: Card = Card {
+  lines: &[Cells(&[Kw]), Block("END_OCTRL")],
+  fields: &[&[KEYWORD]],
+  ownfold: true,
+  keyword: Octrl,
+};
+
+pub static CTRL: Card = Card {
+  lines: &[Cells(&[Kw]), Block("END_CTRL")],
+  fields: &[&[KEYWORD]],
+  ownfold: true,
+  keyword: Ctrl,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_RUNEND_OCTRL: [&str; 19] = [
+    "TITLE /  BoxBeam fine meshed model",
+    "RUNEND/  ",
+    " TIME      15.01",
+    "END_RUNEND",
+    "$",
+    "OCTRL /  ",
+    " THPOUTPUT    POINT     1000",
+    " DSYOUTPUT    STATE       10",
+    "$ Comment",
+    "",
+    "NODPLOT    DFLT",
+    "END_OCTRL",
+    "RUNEND/  ",
+    "END_RUNEND",
+    "CTRL  /  ",
+    " SOMETHING",
+    "NODE  /        1              0.             0.5              0.",
+    "NODE  /        1              0.             0.5              0.",
+    "OCTRL /  ",
+  ];
+
+  #[test]
+  fn fold_runend_octrl() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![
+      (1, 3, Runend),
+      (5, 11, Octrl),
+      (12, 13, Runend),
+      (14, 15, Ctrl),
+      (16, 17, Node),
+    ];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_RUNEND_OCTRL);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  const CARD_HEADER: [&str; 10] = [
+    "INPUTVERSION 2011",
+    "ANALYSIS EXPLICIT    ",
+    "SOLVER    CRASH",
+    "$",
+    "UNIT       MM       KG       MS   KELVIN",
+    "SIGNAL      YES",
+    "$",
+    "TITLE /  BoxBeam fine meshed model                                            ",
+    "RUNEND/  ",
+    "END_RUNEND",
+  ];
+
+  #[test]
+  fn header_cards_are_single_lines() {
+    use card::cell::Value;
+    use carddata::{INPUTVERSION, UNIT};
+    use validation::validate;
+
+    let parsed = INPUTVERSION.parse(&CARD_HEADER);
+    assert_eq!(1, parsed.len());
+    assert_eq!(Value::Integer(2011), parsed[0].cells[1].value);
+
+    let parsed = UNIT.parse(&CARD_HEADER[4..]);
+    assert_eq!(Value::Str("   KELVIN"), parsed[0].cells[4].value);
+
+    assert!(validate(&CARD_HEADER).is_empty());
+  }
+}
@@ -1,10 +1,46 @@
 //! This modules holds the the global static element [`Card`](::card::Card)
 //! instances.
 use card::cell::Cell::*;
+use card::field::*;
 use card::keyword::Keyword::*;
 use card::line::Line::*;
 use card::Card;
 
+const N1: Field = Field::new("IDNOD1", "Node 1");
+const N2: Field = Field::new("IDNOD2", "Node 2");
+const N3: Field = Field::new("IDNOD3", "Node 3");
+const N4: Field = Field::new("IDNOD4", "Node 4");
+const N5: Field = Field::new("IDNOD5", "Node 5");
+const N6: Field = Field::new("IDNOD6", "Node 6");
+const N7: Field = Field::new("IDNOD7", "Node 7");
+const N8: Field = Field::new("IDNOD8", "Node 8");
+const N9: Field = Field::new("IDNOD9", "Node 9");
+const N10: Field = Field::new("IDNOD10", "Node 10");
+const N11: Field = Field::new("IDNOD11", "Node 11");
+const N12: Field = Field::new("IDNOD12", "Node 12");
+const N13: Field = Field::new("IDNOD13", "Node 13");
+const N14: Field = Field::new("IDNOD14", "Node 14");
+const N15: Field = Field::new("IDNOD15", "Node 15");
+const N16: Field = Field::new("IDNOD16", "Node 16");
+const N17: Field = Field::new("IDNOD17", "Node 17");
+const N18: Field = Field::new("IDNOD18", "Node 18");
+const N19: Field = Field::new("IDNOD19", "Node 19");
+const N20: Field = Field::new("IDNOD20", "Node 20");
+const H: Field = Field::new("H", "Thickness, overrides the one of the part")
+  .unit("length");
+const RADIUS: Field = Field::new("RADIUS", "Radius").unit("length");
+const ORIENT: Field = Field::new("IDNOD3", "Orientation node");
+const KJTYP: Field = Field::new("KJTYP", "Type of the kinematic joint");
+const IFRA1: Field = Field::new("IFRA1", "Frame ID at node 1");
+const IFRA2: Field = Field::new("IFRA2", "Frame ID at node 2");
+const IDOF: Field = Field::new("IDOF", "Locked degrees of freedom");
+const IDIMPMA: Field = Field::new("IDIMPMA", "Super element ID");
+const QUALIFY1: Field = Field::new("QUALIFY1", "Qualifier of the matrix");
+const QUALIFY2: Field = Field::new("QUALIFY2", "Qualifier of the matrix");
+const QUALIFY3: Field = Field::new("QUALIFY3", "Qualifier of the matrix");
+const IMATYP: Field = Field::new("IMATYP", "Type of the imported matrix");
+const FNAMEMA: Field = Field::new("FNAMEma", "Name of the matrix file");
+
 pub static SOLID: Card = Card {
   lines: &[
     Cells(&[Kw, Integer(8), Integer(8)]),
@@ -20,6 +56,7 @@ pub static SOLID: Card = Card {
       Integer(8),
     ]),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT], &[BLANK, N1, N2, N3, N4, N5, N6, N7, N8]],
   ownfold: false,
   keyword: Solid,
 };
@@ -51,6 +88,12 @@ pub static HEXA20: Card = Card {
     ]),
     Cells(&[Blank(16), Integer(8), Integer(8), Integer(8), Integer(8)]),
   ],
+  fields: &[
+    &[KEYWORD, IDEL, IDPRT],
+    &[BLANK, N1, N2, N3, N4, N5, N6, N7, N8],
+    &[BLANK, N9, N10, N11, N12, N13, N14, N15, N16],
+    &[BLANK, N17, N18, N19, N20],
+  ],
   ownfold: false,
   keyword: Hexa20,
 };
@@ -80,6 +123,11 @@ pub static PENT15: Card = Card {
       Integer(8),
     ]),
   ],
+  fields: &[
+    &[KEYWORD, IDEL, IDPRT],
+    &[BLANK, N1, N2, N3, N4, N5, N6, N7, N8],
+    &[BLANK, N9, N10, N11, N12, N13, N14, N15],
+  ],
   ownfold: false,
   keyword: Pent15,
 };
@@ -96,6 +144,7 @@ pub static PENTA6: Card = Card {
     Integer(8),
     Integer(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4, N5, N6]],
   ownfold: false,
   keyword: Penta6,
 };
@@ -116,12 +165,18 @@ pub static TETR10: Card = Card {
     ]),
     Cells(&[Blank(16), Integer(8), Integer(8)]),
   ],
+  fields: &[
+    &[KEYWORD, IDEL, IDPRT],
+    &[BLANK, N1, N2, N3, N4, N5, N6, N7, N8],
+    &[BLANK, N9, N10],
+  ],
   ownfold: false,
   keyword: Tetr10,
 };
 
 pub static BAR: Card = Card {
   lines: &[Cells(&[Kw, Integer(8), Integer(8), Integer(8), Integer(8)])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2]],
   ownfold: false,
   keyword: Bar,
 };
@@ -141,6 +196,7 @@ pub static BSHEL: Card = Card {
       Integer(8),
     ]),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT], &[BLANK, N1, N2, N3, N4, N5, N6, N7, N8]],
   ownfold: false,
   keyword: Bshel,
 };
@@ -157,6 +213,7 @@ pub static TSHEL: Card = Card {
     Blank(8),
     Float(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4, BLANK, H]],
   ownfold: false,
   keyword: Tshel,
 };
@@ -173,6 +230,7 @@ pub static SHELL: Card = Card {
     Blank(8),
     Float(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4, BLANK, H]],
   ownfold: false,
   keyword: Shell,
 };
@@ -189,6 +247,7 @@ pub static SHEL6: Card = Card {
     Integer(8),
     Integer(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4, N5, N6]],
   ownfold: false,
   keyword: Shel6,
 };
@@ -206,6 +265,7 @@ pub static SHEL8: Card = Card {
     ]),
     Cells(&[Blank(24), Integer(8), Integer(8), Integer(8), Integer(8)]),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4], &[BLANK, N5, N6, N7, N8]],
   ownfold: false,
   keyword: Shel8,
 };
@@ -223,6 +283,18 @@ pub static MEMBR: Card = Card {
     Float(8),
     Float(8),
   ])],
+  fields: &[&[
+    KEYWORD,
+    IDEL,
+    IDPRT,
+    N1,
+    N2,
+    N3,
+    N4,
+    BLANK,
+    H,
+    Field::new("ALPHA", "Angle of the material direction").unit("angle"),
+  ]],
   ownfold: false,
   keyword: Membr,
 };
@@ -245,6 +317,37 @@ pub static BEAM: Card = Card {
     Cells(&[Blank(8), Float(8), Float(8), Float(8), Float(8), Float(8)]),
     Cells(&[Blank(8), Float(8), Float(8), Float(8), Float(8), Float(8)]),
   ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDEL,
+      IDPRT,
+      N1,
+      N2,
+      ORIENT,
+      BLANK,
+      Field::new("RT1RR1", "Released degrees of freedom at node 1"),
+      BLANK,
+      Field::new("RT2RR2", "Released degrees of freedom at node 2"),
+      IFRA,
+    ],
+    &[
+      BLANK,
+      Field::new("ALPHA1", "Alpha factor at node 1"),
+      Field::new("BETA1", "Beta factor at node 1"),
+      Field::new("GAMMA1", "Gamma factor at node 1"),
+      Field::new("KSI1", "Ksi factor at node 1"),
+      Field::new("ETA1", "Eta factor at node 1"),
+    ],
+    &[
+      BLANK,
+      Field::new("ALPHA2", "Alpha factor at node 2"),
+      Field::new("BETA2", "Beta factor at node 2"),
+      Field::new("GAMMA2", "Gamma factor at node 2"),
+      Field::new("KSI2", "Ksi factor at node 2"),
+      Field::new("ETA2", "Eta factor at node 2"),
+    ],
+  ],
   ownfold: false,
   keyword: Beam,
 };
@@ -259,6 +362,7 @@ pub static SPRGBM: Card = Card {
     Integer(8),
     Integer(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, ORIENT, IFRA]],
   ownfold: false,
   keyword: Sprgbm,
 };
@@ -273,6 +377,7 @@ pub static TETR4: Card = Card {
     Integer(8),
     Integer(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3, N4]],
   ownfold: false,
   keyword: Tetr4,
 };
@@ -288,6 +393,16 @@ pub static SPRING: Card = Card {
     Integer(8),
     IntegerorBlank(8),
   ])],
+  fields: &[&[
+    KEYWORD,
+    IDEL,
+    IDPRT,
+    N1,
+    N2,
+    ORIENT,
+    IFRA,
+    Field::new("ISENS", "Sensor ID, always active if blank"),
+  ]],
   ownfold: false,
   keyword: Spring,
 };
@@ -305,6 +420,18 @@ pub static JOINT: Card = Card {
     Float(8),
     Integer(8),
   ])],
+  fields: &[&[
+    KEYWORD,
+    IDEL,
+    IDPRT,
+    N1,
+    N2,
+    IFRA,
+    Field::new("IDNOD3", "Orientation node, none if blank"),
+    Field::new("K", "Stiffness scale factor").default("1."),
+    Field::new("D", "Damping scale factor").default("1."),
+    Field::new("ISENS", "Sensor ID"),
+  ]],
   ownfold: false,
   keyword: Joint,
 };
@@ -324,6 +451,20 @@ pub static KJOIN: Card = Card {
     ]),
     Cells(&[Blank(10), Binary(6)]),
   ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDEL,
+      IDPRT,
+      KJTYP,
+      N1,
+      N2,
+      IFRA1,
+      IFRA2,
+      Field::new("LREF", "Reference length").unit("length"),
+    ],
+    &[BLANK, IDOF],
+  ],
   ownfold: false,
   keyword: Kjoin,
 };
@@ -342,18 +483,24 @@ pub static MTOJNT: Card = Card {
     ]),
     Cells(&[Blank(10), Binary(6)]),
   ],
+  fields: &[
+    &[KEYWORD, IDEL, IDPRT, KJTYP, N1, N2, IFRA1, IFRA2],
+    &[BLANK, IDOF],
+  ],
   ownfold: false,
   keyword: Mtojnt,
 };
 
 pub static SPHEL: Card = Card {
   lines: &[Cells(&[Kw, Integer(8), Integer(8), Integer(8), Float(8)])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, RADIUS]],
   ownfold: false,
   keyword: Sphel,
 };
 
 pub static SPHELO: Card = Card {
   lines: &[Cells(&[Kw, Integer(8), Integer(8), Integer(8), Float(8)])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, RADIUS]],
   ownfold: false,
   keyword: Sphelo,
 };
@@ -367,6 +514,7 @@ pub static GAP: Card = Card {
     Integer(8),
     Integer(8),
   ])],
+  fields: &[&[KEYWORD, IDEL, IDPRT, N1, N2, N3]],
   ownfold: false,
   keyword: Gap,
 };
@@ -377,6 +525,11 @@ pub static IMPMA: Card = Card {
     Cells(&[Fixed("NAME"), Str(76)]),
     Cells(&[Blank(8), Str(76)]),
   ],
+  fields: &[
+    &[KEYWORD, IDIMPMA, QUALIFY1, QUALIFY2, QUALIFY3, IMATYP],
+    &[NAME, TITLE],
+    &[BLANK, FNAMEMA],
+  ],
   ownfold: false,
   keyword: Impma,
 };
@@ -384,7 +537,7 @@ pub static IMPMA: Card = Card {
 #[cfg(test)]
 mod tests {
 
-  const CARD_SOLID: [&'static str; 15] = [
+  const CARD_SOLID: [&str; 15] = [
     "$SOLID Element",
     "SOLID /                                                                        ",
     "                                                                                ",
@@ -415,7 +568,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_HEXA20: [&'static str; 18] = [
+  const CARD_HEXA20: [&str; 18] = [
     "$HEXA20 Element",
     "HEXA20/                 ",
     "                                                                                ",
@@ -449,7 +602,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PENT15: [&'static str; 14] = [
+  const CARD_PENT15: [&str; 14] = [
     "$PENTA15 Element",
     "PENT15/                 ",
     "                                                                                ",
@@ -479,7 +632,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PENTA6: [&'static str; 9] = [
+  const CARD_PENTA6: [&str; 9] = [
     "$PENTA6 Element ",
     "PENTA6/                                                                 ",
     "PENTA6/                                                                 ",
@@ -504,7 +657,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_TETR10: [&'static str; 13] = [
+  const CARD_TETR10: [&str; 13] = [
     "$TETR10 Element",
     "TETR10/                 ",
     "                                                                                ",
@@ -533,7 +686,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_TETR4: [&'static str; 6] = [
+  const CARD_TETR4: [&str; 6] = [
     "$TETR4 Element",
     "TETR4 /                                                 ",
     "TETR4 /                                                 ",
@@ -555,7 +708,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_BSHEL: [&'static str; 6] = [
+  const CARD_BSHEL: [&str; 6] = [
     "BSHEL /                 ",
     "                                                                                ",
     "BSHEL /                 ",
@@ -577,7 +730,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_TSHEL: [&'static str; 9] = [
+  const CARD_TSHEL: [&str; 9] = [
     "$TSHEL Element",
     "TSHEL /                                                                 ",
     "TSHEL /                                                                 ",
@@ -602,7 +755,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SHELL: [&'static str; 9] = [
+  const CARD_SHELL: [&str; 9] = [
     "$SHELL Element",
     "SHELL /                                                                 ",
     "SHELL /                                                                 ",
@@ -627,7 +780,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SHEL6: [&'static str; 9] = [
+  const CARD_SHEL6: [&str; 9] = [
     "$SHEL6 Element",
     "SHEL6 /                                                                 ",
     "SHEL6 /                                                                 ",
@@ -652,7 +805,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SHEL8: [&'static str; 8] = [
+  const CARD_SHEL8: [&str; 8] = [
     "$SHEL8 Element",
     "SHEL8 /                                                 ",
     "                                                        ",
@@ -676,7 +829,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_MEMBR: [&'static str; 9] = [
+  const CARD_MEMBR: [&str; 9] = [
     "$MEMBR Element",
     "MEMBR /                                                                 ",
     "MEMBR /                                                                 ",
@@ -701,7 +854,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_BEAM: [&'static str; 14] = [
+  const CARD_BEAM: [&str; 14] = [
     "$BEAM Element",
     "BEAM  /                                                                         ",
     "                                                ",
@@ -731,7 +884,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SPRGBM: [&'static str; 9] = [
+  const CARD_SPRGBM: [&str; 9] = [
     "$SPRGBM Element",
     "SPRGBM/                                                                 ",
     "SPRGBM/                                                                 ",
@@ -756,7 +909,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_BAR: [&'static str; 6] = [
+  const CARD_BAR: [&str; 6] = [
     "$BAR  Element",
     "BAR   /                                                 ",
     "BAR   /                                                 ",
@@ -778,7 +931,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SPRING: [&'static str; 7] = [
+  const CARD_SPRING: [&str; 7] = [
     "$SPRING  Element",
     "SPRING/                                                 ",
     "SPRING/                                                 ",
@@ -801,7 +954,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_JOINT: [&'static str; 3] = [
+  const CARD_JOINT: [&str; 3] = [
     "JOINT /                                                 ",
     "JOINT /                                                 ",
     "JOINT /                                                 ",
@@ -820,7 +973,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_KJOIN: [&'static str; 8] = [
+  const CARD_KJOIN: [&str; 8] = [
     "$KJOIN Element",
     "KJOIN /                                                                 ",
     "                ",
@@ -844,7 +997,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_MTOJNT: [&'static str; 7] = [
+  const CARD_MTOJNT: [&str; 7] = [
     "$MTOJNTElement",
     "MTOJNT/                                                                 ",
     "                ",
@@ -867,7 +1020,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SPHEL: [&'static str; 6] = [
+  const CARD_SPHEL: [&str; 6] = [
     "SPHEL /                                                                 ",
     "SPHEL /                                                                 ",
     "$SPHEL Element",
@@ -889,7 +1042,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SPHELO: [&'static str; 2] = [
+  const CARD_SPHELO: [&str; 2] = [
     "SPHELO/                                                                 ",
     "SPHELO/                                                                 ",
   ];
@@ -907,7 +1060,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_GAP: [&'static str; 6] = [
+  const CARD_GAP: [&str; 6] = [
     "GAP   /                                                                 ",
     "GAP   /                                                                 ",
     "GAP   /                                                                 ",
@@ -929,7 +1082,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_IMPMA: [&'static str; 14] = [
+  const CARD_IMPMA: [&str; 14] = [
     "$IMPMA Super Element Matrix Import",
     "$#       IDIMPMAQUALIFY1QUALIFY2QUALIFY3  IMATYP    ISEL",
     "IMPMA /        1                               0         ",
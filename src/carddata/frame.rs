@@ -0,0 +1,122 @@
+//! This modules holds the the global static local frame
+//! [`Card`](::card::Card) instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::keyword::Keyword::*;
+use card::line::Case;
+use card::line::Conditional::*;
+use card::line::Line::*;
+use card::Card;
+
+/// A local coordinate frame. It is either given by its origin, the direction
+/// of its x axis and a direction in its xy plane (`ITYP` 0), or by three
+/// nodes: the origin, a node on the x axis and a node in the xy plane (`ITYP`
+/// 1). A frame given by nodes moves with them.
+pub static FRAME: Card = Card {
+  lines: &[
+    Provides(&[Kw, Integer(8), Integer(8)], Number(16..24)),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Switch(
+      0,
+      &[
+        // Frame by vectors
+        Case {
+          value: 0,
+          lines: &[
+            Cells(&[Float(16), Float(16), Float(16)]),
+            Cells(&[Float(16), Float(16), Float(16)]),
+            Cells(&[Float(16), Float(16), Float(16)]),
+          ],
+          fields: &[
+            &[
+              Field::new("X0", "X coordinate of the origin").unit("length"),
+              Field::new("Y0", "Y coordinate of the origin").unit("length"),
+              Field::new("Z0", "Z coordinate of the origin").unit("length"),
+            ],
+            &[
+              Field::new("X1", "X component of the x axis"),
+              Field::new("Y1", "Y component of the x axis"),
+              Field::new("Z1", "Z component of the x axis"),
+            ],
+            &[
+              Field::new("X2", "X component of a vector in the xy plane"),
+              Field::new("Y2", "Y component of a vector in the xy plane"),
+              Field::new("Z2", "Z component of a vector in the xy plane"),
+            ],
+          ],
+        },
+        // Frame by nodes
+        Case {
+          value: 1,
+          lines: &[Cells(&[Integer(8), Integer(8), Integer(8)])],
+          fields: &[&[
+            Field::new("IDNOD1", "Node ID of the origin"),
+            Field::new("IDNOD2", "Node ID on the x axis"),
+            Field::new("IDNOD3", "Node ID in the xy plane"),
+          ]],
+        },
+      ],
+    ),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDFRA", "Frame ID"),
+      Field::new("ITYP", "Frame definition type").default("0"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Frame,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_FRAME: [&str; 14] = [
+    "$#         IDFRA    ITYP",
+    "FRAME /        1       0",
+    "$#                                                                         TITLE",
+    "NAME Rotated",
+    "              0.              0.              0.",
+    "              0.              1.              0.",
+    "             -1.              0.              0.",
+    "FRAME /        2       1",
+    "NAME Moving",
+    "$#IDNOD1  IDNOD2  IDNOD3",
+    "       1       2       3",
+    "FRAME /        3       1",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_frame() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![(1, 6, Frame), (7, 10, Frame)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_FRAME);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_frame() {
+    use card::cell::Value;
+    use carddata::FRAME;
+
+    let parsed = FRAME.parse(&CARD_FRAME[1..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 3, 4, 5, 6], v);
+    assert_eq!(Value::Float(-1.), parsed[4].cells[0].value);
+    assert_eq!(Some("X2"), FRAME.field(parsed[4].line, 0).map(|f| f.name));
+
+    let parsed = FRAME.parse(&CARD_FRAME[7..]);
+    assert_eq!(3, parsed.len());
+    assert_eq!(Value::Integer(3), parsed[2].cells[2].value);
+  }
+}
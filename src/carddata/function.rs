@@ -0,0 +1,103 @@
+//! This modules holds the the global static function [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::keyword::Keyword::*;
+use card::line::Conditional::*;
+use card::line::Line::*;
+use card::Card;
+
+/// A load curve. The number of points is given on the keyword line, the
+/// points follow the title line with 2 points per line.
+pub static FUNCT: Card = Card {
+  lines: &[
+    Provides(
+      &[
+        Kw,
+        Integer(8),
+        Integer(8),
+        Float(10),
+        Float(10),
+        Float(10),
+        Float(10),
+      ],
+      Count(16..24, 2),
+    ),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Repeat(&[Float(20), Float(20), Float(20), Float(20)], 0),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDFUN", "Function ID"),
+      Field::new("NPTS", "Number of points"),
+      Field::new("SCALX", "Scale factor of the abscissa").default("1."),
+      Field::new("SCALY", "Scale factor of the ordinate").default("1."),
+      Field::new("SHIFX", "Shift of the abscissa").default("0."),
+      Field::new("SHIFY", "Shift of the ordinate").default("0."),
+    ],
+    &[NAME, TITLE],
+    &[
+      Field::new("ABSC1", "Abscissa of the first point"),
+      Field::new("ORDI1", "Ordinate of the first point"),
+      Field::new("ABSC2", "Abscissa of the second point"),
+      Field::new("ORDI2", "Ordinate of the second point"),
+    ],
+  ],
+  ownfold: true,
+  keyword: Funct,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_FUNCT: [&str; 16] = [
+    "$#         IDFUN    NPTS     SCALX     SCALY     SHIFX     SHIFY",
+    "FUNCT /        1       5        1.        1.        0.        0.",
+    "$#                                                                         TITLE",
+    "NAME Impactor velocity",
+    "$#              ABSC1               ORDI1               ABSC2               ORDI2",
+    "                  0.                  0.                 0.1                 1.5",
+    "                 0.2                 2.5                 0.5                 3.5",
+    "$ last point",
+    "                  1.                  4.",
+    "FUNCT /        2       2",
+    "NAME Ramp",
+    "                  0.                  0.                  1.                  1.",
+    "FUNCT /        3       4",
+    "NAME Too short",
+    "                  0.                  0.                  1.                  1.",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_funct() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> =
+      vec![(1, 8, Funct), (9, 11, Funct), (12, 14, Funct)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_FUNCT);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_funct() {
+    use card::cell::Value;
+    use carddata::FUNCT;
+
+    let parsed = FUNCT.parse(&CARD_FUNCT[1..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 3, 5, 6, 8], v);
+    assert_eq!(Value::Float(3.5), parsed[3].cells[3].value);
+    assert_eq!(Value::Blank, parsed[4].cells[2].value);
+    assert_eq!(
+      Some("ORDI2"),
+      FUNCT.field(parsed[4].line, 3).map(|f| f.name)
+    );
+  }
+}
@@ -0,0 +1,46 @@
+//! This modules holds the the global static group [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::ges::GesType::*;
+use card::keyword::Keyword::*;
+use card::line::Line::*;
+use card::Card;
+
+/// A named group of entities. The name is given on the title line, and is
+/// used to reference the group in a GES, e.g. `GRP 'name'`.
+pub static GROUP: Card = Card {
+  lines: &[Cells(&[Kw]), Cells(&[Fixed("NAME"), Str(76)]), Ges(GesEle)],
+  fields: &[&[KEYWORD], &[NAME, Field::new("NAME", "Name of the group")]],
+  ownfold: true,
+  keyword: Group,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_GROUP: [&str; 9] = [
+    "GROUP / ",
+    "NAME shells",
+    "        PART 1",
+    "        ELE 100 101",
+    "        END",
+    "$",
+    "GROUP / ",
+    "NAME nodes",
+    "        END",
+  ];
+
+  #[test]
+  fn fold_group() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![(0, 4, Group), (6, 8, Group)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_GROUP);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+}
@@ -1,16 +1,23 @@
 //! This modules holds the the global static link [`Card`](::card::Card)
 //! instances.
 use card::cell::Cell::*;
+use card::field::*;
 use card::ges::GesType::*;
 use card::keyword::Keyword::*;
 use card::line::Line::*;
 use card::Card;
 
+const IDNOD: Field = Field::new("IDNOD", "Node ID");
+const MORE: Field =
+  Field::new("MORE", "Number of additional nodes, 0 for one node").default("0");
+const NLAYR: Field = Field::new("NLAYR", "Number of connected layers");
+
 pub static ELINK: Card = Card {
   lines: &[
     Cells(&[Kw, Integer(8), Integer(8), Integer(8), Integer(8)]),
     Ges(GesFace),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT, IDNOD, NLAYR]],
   ownfold: false,
   keyword: Elink,
 };
@@ -20,6 +27,7 @@ pub static LLINK: Card = Card {
     Cells(&[Kw, Integer(8), Integer(8), Integer(8), Integer(8)]),
     Ges(GesFace),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT, IDNOD, NLAYR]],
   ownfold: false,
   keyword: Llink,
 };
@@ -37,6 +45,15 @@ pub static SLINK: Card = Card {
     ]),
     Ges(GesFace),
   ],
+  fields: &[&[
+    KEYWORD,
+    IDEL,
+    IDPRT,
+    Field::new("IDNOD1", "Node 1"),
+    Field::new("IDNOD2", "Node 2"),
+    MORE,
+    NLAYR,
+  ]],
   ownfold: false,
   keyword: Slink,
 };
@@ -53,6 +70,14 @@ pub static PLINK: Card = Card {
     ]),
     Ges(GesFace),
   ],
+  fields: &[&[
+    KEYWORD,
+    IDEL,
+    IDPRT,
+    IDNOD,
+    MORE,
+    NLAYR,
+  ]],
   ownfold: false,
   keyword: Plink,
 };
@@ -64,6 +89,7 @@ pub static TIED: Card = Card {
     Ges(GesNode),
     Ges(GesFace),
   ],
+  fields: &[&[KEYWORD, IDEL, IDPRT, NLAYR], &[NAME, TITLE]],
   ownfold: false,
   keyword: Tied,
 };
@@ -71,7 +97,7 @@ pub static TIED: Card = Card {
 #[cfg(test)]
 mod tests {
 
-  const CARD_ELINK: [&'static str; 13] = [
+  const CARD_ELINK: [&str; 13] = [
     "$ELINK Element",
     "ELINK /                                 ",
     "        PART ",
@@ -100,7 +126,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_LLINK: [&'static str; 23] = [
+  const CARD_LLINK: [&str; 23] = [
     "$LLINK Element",
     "LLINK /                                 ",
     "        PART 1",
@@ -139,7 +165,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_SLINK: [&'static str; 6] = [
+  const CARD_SLINK: [&str; 6] = [
     "$SLINK Element",
     "SLINK /                                 ",
     "        PART 1",
@@ -161,7 +187,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PLINK: [&'static str; 9] = [
+  const CARD_PLINK: [&str; 9] = [
     "$PLINK Element",
     "PLINK /                                 ",
     "        PART 47",
@@ -186,7 +212,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_TIED: [&'static str; 22] = [
+  const CARD_TIED: [&str; 22] = [
     "$TIED Element",
     "TIED  /                         ",
     "NAME TIED",
@@ -0,0 +1,368 @@
+//! This modules holds the the global static material [`Card`](::card::Card)
+//! instances.
+use card::cell::Cell::*;
+use card::field::*;
+use card::keyword::Keyword::*;
+use card::line::Conditional::*;
+use card::line::Line::*;
+use card::line::Case;
+use card::Card;
+
+const IDMAT: Field = Field::new("IDMAT", "Material ID");
+const E: Field = Field::new("E", "Young's modulus").unit("force/length^2");
+const NU: Field = Field::new("NU", "Poisson's ratio");
+const SIGMAY: Field =
+  Field::new("SIGMAy", "Initial yield stress").unit("force/length^2");
+const ET: Field = Field::new("Et", "Tangent modulus").unit("force/length^2");
+const IDFUNC: Field = Field::new("IDFUNC", "Yield curve function ID");
+const SCALE: Field = Field::new("SCALE", "Scale factor of the yield curve");
+const EPSIPMAX: Field = Field::new("EPSIpmax", "Plastic strain at rupture");
+const STRAT1: Field = Field::new("STRAT1", "Strain rate parameter 1");
+const STRAT2: Field = Field::new("STRAT2", "Strain rate parameter 2");
+
+pub static MATER: Card = Card {
+  lines: &[
+    Provides(
+      &[
+        Kw,
+        Integer(8),
+        Integer(8),
+        Float(16),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+        Integer(8),
+      ],
+      Number(16..24),
+    ),
+    Cells(&[Blank(56), Float(8), Integer(8), Integer(8)]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Switch(
+      0,
+      &[
+        // Elastic-plastic solid
+        Case {
+          value: 1,
+          lines: &[
+            Cells(&[Float(10), Float(10), Float(10), Float(10), Blank(40)]),
+            Cells(&[Integer(10), Float(10), Blank(60)]),
+            Cells(&[Float(10), Float(10), Float(10), Blank(50)]),
+          ],
+          fields: &[
+            &[E, SIGMAY, NU, ET, BLANK],
+            &[IDFUNC, SCALE, BLANK],
+            &[EPSIPMAX, STRAT1, STRAT2, BLANK],
+          ],
+        },
+        // Elastic-plastic solid with ductile damage
+        Case {
+          value: 16,
+          lines: &[
+            Cells(&[Float(10), Float(10), Float(10), Float(10), Blank(40)]),
+            Cells(&[Integer(10), Float(10), Blank(60)]),
+            Cells(&[Float(10), Float(10), Float(10), Float(10), Blank(40)]),
+            Cells(&[Float(10), Float(10), Blank(60)]),
+          ],
+          fields: &[
+            &[E, SIGMAY, NU, ET, BLANK],
+            &[IDFUNC, SCALE, BLANK],
+            &[
+              Field::new("EPSIi", "Plastic strain at damage initiation"),
+              Field::new("EPSI1", "Plastic strain at intermediate damage"),
+              Field::new("D1", "Intermediate damage"),
+              Field::new("EPSIu", "Plastic strain at ultimate damage"),
+              BLANK,
+            ],
+            &[STRAT1, STRAT2, BLANK],
+          ],
+        },
+        // Elastic
+        Case {
+          value: 100,
+          lines: &[Cells(&[Float(10), Blank(10), Float(10), Blank(10)])],
+          fields: &[&[E, BLANK, NU, BLANK]],
+        },
+        // Elastic-plastic shell
+        Case {
+          value: 103,
+          lines: &[
+            Cells(&[
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+            ]),
+            Cells(&[Float(10), Float(10), Float(10), Float(10)]),
+            Cells(&[Blank(80)]),
+            Cells(&[
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Blank(30),
+            ]),
+            Cells(&[
+              Blank(20),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+            ]),
+            Cells(&[Str(10), Float(10), Blank(60)]),
+          ],
+          fields: &[
+            &[
+              E,
+              SIGMAY,
+              NU,
+              Field::new("ALPHA", "Thermal expansion coefficient"),
+              Field::new("HGM", "Membrane hourglass coefficient"),
+              Field::new("HGW", "Out-of-plane hourglass coefficient"),
+              Field::new("HGQ", "Rotation hourglass coefficient"),
+              Field::new("As", "Transverse shear correction factor"),
+            ],
+            &[
+              Field::new("E1", "Tangent modulus of the first segment")
+                .unit("force/length^2"),
+              Field::new("SIGMA1", "End stress of the first segment")
+                .unit("force/length^2"),
+              Field::new("E2", "Tangent modulus of the second segment")
+                .unit("force/length^2"),
+              Field::new("SIGMA2", "End stress of the second segment")
+                .unit("force/length^2"),
+            ],
+            &[BLANK],
+            &[
+              EPSIPMAX,
+              STRAT1,
+              STRAT2,
+              Field::new("REL_THIN", "Relative thinning at rupture"),
+              Field::new("REL_THIC", "Relative thickening at rupture"),
+              BLANK,
+            ],
+            &[
+              BLANK,
+              Field::new("STRAT3", "Strain rate parameter 3"),
+              Field::new("STRAT4", "Strain rate parameter 4"),
+              Field::new("STRAT5", "Strain rate parameter 5"),
+              Field::new("STRAT6", "Strain rate parameter 6"),
+              Field::new("KSI", "Damping factor"),
+              Field::new("Fo", "Strain rate filter frequency"),
+            ],
+            &[
+              Field::new("GRUC_KW", "Rupture criterion keyword"),
+              Field::new("GRUC_VAL", "Rupture criterion value"),
+              BLANK,
+            ],
+          ],
+        },
+        // Spotweld
+        Case {
+          value: 302,
+          lines: &[
+            Cells(&[
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Float(10),
+              Integer(10),
+              Blank(10),
+              Float(10),
+            ]),
+            Cells(&[Integer(10), Float(10), Integer(10)]),
+          ],
+          fields: &[
+            &[
+              Field::new("SLFACM", "Scale factor for the contact stiffness"),
+              Field::new("FSNVL", "Maximum contact force").unit("force"),
+              Field::new("DELTNL", "Penetration at maximum force")
+                .unit("length"),
+              Field::new("STNOR", "Normal failure force").unit("force"),
+              Field::new("STTAN", "Tangential failure force").unit("force"),
+              Field::new("IFLGC", "Contact stiffness flag"),
+              BLANK,
+              Field::new("TLSTIF", "Time of stiffness increase").unit("time"),
+            ],
+            &[
+              Field::new("I3DOF", "Translational degrees of freedom only"),
+              Field::new("TOLCOR", "Tolerance for the connection")
+                .unit("length"),
+              Field::new("IDRUP", "Rupture model ID"),
+            ],
+          ],
+        },
+      ],
+    ),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      IDMAT,
+      Field::new("MATYP", "Material type"),
+      Field::new("RHO", "Density").unit("mass/length^3"),
+      Field::new("ISINT", "Solid integration flag").default("0"),
+      Field::new("ISHG", "Solid hourglass flag").default("0"),
+      Field::new("ISTRAT", "Strain rate flag").default("0"),
+      Field::new("IFROZ", "Frozen metric flag").default("0"),
+    ],
+    &[
+      BLANK,
+      Field::new("QVM", "Artificial viscosity factor").default("1."),
+      Field::new("THDID", "Thermal material ID").default("0"),
+      Field::new("IDMPD", "Material damping ID").default("0"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Mater,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_MATER: [&str; 44] = [
+    "$#         IDMAT   MATYP             RHO   ISINT    ISHG  ISTRAT   IFROZ",
+    "MATER /        3     103         7.85E-6       0       0       0       0",
+    "$# BLANK                                                     QVM   THDID   IDMPD",
+    "                                                              1.       0       0",
+    "$#                                                                         TITLE",
+    "NAME Material: Box section                                                      ",
+    "$#       E    SIGMAy        NU     ALPHA       HGM       HGW       HGQ        As",
+    "      210.       0.2       0.3                0.01      0.01      0.01      0.83",
+    "$#      E1    SIGMA1        E2    SIGMA2",
+    "        1.       0.3        0.        0.",
+    "$#",
+    "",
+    "$#EPSIpmax    STRAT1    STRAT2  REL_THIN  REL_THIC                         BLANK",
+    "        0.        0.        0.        0.                                        ",
+    "$#             BLANK    STRAT3    STRAT4    STRAT5    STRAT6       KSI        Fo",
+    "                            0.        0.        0.        0.        0.        0.",
+    "$# GRUC_KW  GRUC_VAL                                                       BLANK",
+    "THIC             0.9                                                            ",
+    "$ impactor",
+    "$#         IDMAT   MATYP             RHO   ISINT    ISHG  ISTRAT   IFROZ",
+    "MATER /        4     100         7.85E-6       0       0       0       0",
+    "$# BLANK                                                     QVM   THDID   IDMPD",
+    "                                                              1.       0       0",
+    "$#                                                                         TITLE",
+    "NAME Materials: Impactor                                                        ",
+    "$#       E     BLANK        NU     BLANK",
+    "      210.                 0.3          ",
+    "$",
+    "$#         IDMAT   MATYP             RHO   ISINT    ISHG  ISTRAT   IFROZ",
+    "MATER /        5     302              0.       0       0       0       0",
+    "$# BLANK                                                     QVM   THDID   IDMPD",
+    "                                                              1.       0       0",
+    "$#                                                                         TITLE",
+    "NAME Spotweld definition_1 Material                                             ",
+    "$#  SLFACM     FSNVL    DELTNL     STNOR     STTAN     IFLGC     BLANK    TLSTIF",
+    "        0.        0.        0.        0.        0.         0                    ",
+    "$#   I3DOF    TOLCOR     IDRUP",
+    "         0        0.         0",
+    "$",
+    "MATER /        6     999         7.85E-6       0       0       0       0",
+    "                                                              1.       0       0",
+    "NAME Unknown type",
+    "      210.",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_mater() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> =
+      vec![(1, 17, Mater), (20, 26, Mater), (29, 37, Mater), (39, 41, Mater)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_MATER);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_mater() {
+    use card::cell::Value;
+    use carddata::MATER;
+
+    let parsed = MATER.parse(&CARD_MATER[1..]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 3, 5, 7, 9, 11, 13, 15, 17], v);
+    assert_eq!(Value::Float(0.83), parsed[3].cells[7].value);
+    assert_eq!(Value::Str("THIC"), parsed[8].cells[0].value);
+    assert_eq!(
+      Some("GRUC_VAL"),
+      MATER.field(parsed[8].line, 1).map(|f| f.name)
+    );
+  }
+
+  const CARD_MATER_SOLID: [&str; 20] = [
+    "$#         IDMAT   MATYP             RHO   ISINT    ISHG  ISTRAT   IFROZ",
+    "MATER /        7       1         7.85E-6       0       0       0       0",
+    "                                                              1.       0       0",
+    "NAME Steel solid",
+    "$#       E    SIGMAy        NU        Et                                   BLANK",
+    "      210.       0.3       0.3        1.                                        ",
+    "$#  IDFUNC     SCALE                                                       BLANK",
+    "        12        1.                                                            ",
+    "$#EPSIpmax    STRAT1    STRAT2                                             BLANK",
+    "       0.2        0.        0.                                                  ",
+    "MATER /        8      16         7.85E-6       0       0       0       0",
+    "                                                              1.       0       0",
+    "NAME Steel solid with damage",
+    "      210.       0.3       0.3        1.                                        ",
+    "        12        1.                                                            ",
+    "$#   EPSIi     EPSI1        D1     EPSIu                                   BLANK",
+    "      0.05       0.1       0.3       0.2                                        ",
+    "$#  STRAT1    STRAT2                                                       BLANK",
+    "        0.        0.                                                            ",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_mater_solid() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![(1, 9, Mater), (10, 18, Mater)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_MATER_SOLID);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  #[test]
+  fn parse_mater_solid() {
+    use card::cell::Value;
+    use carddata::MATER;
+
+    let parsed = MATER.parse(&CARD_MATER_SOLID[1..10]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 1).collect();
+
+    assert_eq!(vec![1, 2, 3, 5, 7, 9], v);
+    assert_eq!(Value::Float(1.), parsed[3].cells[3].value);
+    assert_eq!(Value::Integer(12), parsed[4].cells[0].value);
+    assert_eq!(Some("Et"), MATER.field(parsed[3].line, 3).map(|f| f.name));
+
+    let parsed = MATER.parse(&CARD_MATER_SOLID[10..19]);
+    let v: Vec<usize> = parsed.iter().map(|p| p.index + 10).collect();
+
+    assert_eq!(vec![10, 11, 12, 13, 14, 16, 18], v);
+    assert_eq!(Value::Float(0.3), parsed[5].cells[2].value);
+    assert_eq!(
+      Some("EPSIu"),
+      MATER.field(parsed[5].line, 3).map(|f| f.name)
+    );
+  }
+}
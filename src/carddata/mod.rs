@@ -1,14 +1,32 @@
 //! This module contains all the global static [`Card`](::card::Card) instances
+pub mod boundary;
+pub mod constraint;
+pub mod contact;
+pub mod control;
 pub mod element;
+pub mod frame;
+pub mod function;
+pub mod group;
 pub mod link;
+pub mod mater;
 pub mod node;
+pub mod output;
 pub mod part;
 
+pub use self::boundary::*;
+pub use self::constraint::*;
+pub use self::contact::*;
+pub use self::control::*;
 pub use self::element::*;
+pub use self::frame::*;
+pub use self::function::*;
+pub use self::group::*;
 pub use self::link::*;
+pub use self::mater::*;
 /// All static declarations can be imported via
 /// ```rust, ignore
 /// use carddata::*;
 /// ```
 pub use self::node::*;
+pub use self::output::*;
 pub use self::part::*;
@@ -1,20 +1,48 @@
 //! This modules holds the the global static node [`Card`](::card::Card)
 //! instances.
 use card::cell::Cell::*;
+use card::field::*;
 use card::ges::GesType::*;
 use card::keyword::Keyword::*;
 use card::line::Conditional::*;
 use card::line::Line::*;
 use card::Card;
 
+const IDNOD: Field = Field::new("IDNOD", "Node ID");
+const X: Field = Field::new("X", "X coordinate").unit("length");
+const Y: Field = Field::new("Y", "Y coordinate").unit("length");
+const Z: Field = Field::new("Z", "Z coordinate").unit("length");
+
+const NODE_FIELDS: &[&[Field]] = &[&[KEYWORD, IDNOD, X, Y, Z]];
+
+const NSMAS_FIELDS: &[&[Field]] = &[
+  &[
+    KEYWORD,
+    Field::new("IDNODMS", "Nonstructural mass ID"),
+    Field::new("MASS", "Total mass").unit("mass").default("0."),
+    Field::new("MLEN", "Mass per unit length")
+      .unit("mass/length")
+      .default("0."),
+    Field::new("MARE", "Mass per unit area")
+      .unit("mass/length^2")
+      .default("0."),
+    Field::new("MVOL", "Mass per unit volume")
+      .unit("mass/length^3")
+      .default("0."),
+  ],
+  &[NAME, TITLE],
+];
+
 pub static NODE: Card = Card {
-  lines: &[Cells(&[Kw, Integer(16), Float(16), Float(16), Float(16)])],
+  lines: &[Cells(&[Kw, Integer(8), Float(16), Float(16), Float(16)])],
+  fields: NODE_FIELDS,
   ownfold: false,
   keyword: Node,
 };
 
 pub static CNODE: Card = Card {
-  lines: &[Cells(&[Kw, Integer(16), Float(16), Float(16), Float(16)])],
+  lines: &[Cells(&[Kw, Integer(8), Float(16), Float(16), Float(16)])],
+  fields: NODE_FIELDS,
   ownfold: false,
   keyword: Cnode,
 };
@@ -31,6 +59,36 @@ pub static MASS: Card = Card {
     Optional(&[Blank(8), Float(16), Float(16), Float(16)], 0),
     Ges(GesNode),
   ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDNOD", "Node ID, 0 to use the GES"),
+      IFRA,
+      Field::new("DISr", "Translational mass in r direction").unit("mass"),
+      Field::new("DISs", "Translational mass in s direction").unit("mass"),
+      Field::new("DISt", "Translational mass in t direction").unit("mass"),
+    ],
+    &[NAME, TITLE],
+    &[
+      Field::new("Mx", "Translational mass in x direction").unit("mass"),
+      Field::new("My", "Translational mass in y direction").unit("mass"),
+      Field::new("Mz", "Translational mass in z direction").unit("mass"),
+    ],
+    &[
+      BLANK,
+      Field::new("Ix", "Rotational inertia about x").unit("mass*length^2"),
+      Field::new("Iy", "Rotational inertia about y").unit("mass*length^2"),
+      Field::new("Iz", "Rotational inertia about z").unit("mass*length^2"),
+      BLANK,
+      CONT,
+    ],
+    &[
+      BLANK,
+      Field::new("Ixy", "Product of inertia xy").unit("mass*length^2"),
+      Field::new("Iyz", "Product of inertia yz").unit("mass*length^2"),
+      Field::new("Ixz", "Product of inertia xz").unit("mass*length^2"),
+    ],
+  ],
   ownfold: true,
   keyword: Mass,
 };
@@ -41,6 +99,7 @@ pub static NSMAS: Card = Card {
     Cells(&[Fixed("NAME"), Str(76)]),
     Ges(GesEle),
   ],
+  fields: NSMAS_FIELDS,
   ownfold: true,
   keyword: Nsmas,
 };
@@ -51,6 +110,7 @@ pub static NSMAS2: Card = Card {
     Cells(&[Fixed("NAME"), Str(76)]),
     Ges(GesEle),
   ],
+  fields: NSMAS_FIELDS,
   ownfold: true,
   keyword: Nsmas2,
 };
@@ -58,7 +118,7 @@ pub static NSMAS2: Card = Card {
 #[cfg(test)]
 mod tests {
 
-  const CARD_NSMAS: [&'static str; 7] = [
+  const CARD_NSMAS: [&str; 7] = [
     "$ NSMAS - Nonstructural mass",
     "$#       IDNODMS            MASS            MLEN            MARE            MVOL",
     "NSMAS /        1              0.                                                ",
@@ -85,7 +145,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_MASS: [&'static str; 10] = [
+  const CARD_MASS: [&str; 10] = [
     "$ MASS Card",
     "$#         IDNOD    IFRA   Blank            DISr            DISs            DISt",
     "MASS  /        0       0                                                        ",
@@ -111,4 +171,41 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
+  #[test]
+  fn parse_node() {
+    use card::cell::Value;
+    use carddata::NODE;
+
+    let lines = [
+      "NODE  /       28     30.29999924            50.5              0.",
+    ];
+    let parsed = NODE.parse(&lines);
+    let values: Vec<&Value> =
+      parsed[0].cells.iter().map(|c| &c.value).collect();
+
+    assert_eq!(1, parsed.len());
+    assert_eq!(
+      vec![
+        &Value::Keyword("NODE  /"),
+        &Value::Integer(28),
+        &Value::Float(30.29999924),
+        &Value::Float(50.5),
+        &Value::Float(0.),
+      ],
+      values
+    );
+  }
+
+  #[test]
+  fn parse_mass() {
+    use card::cell::Value;
+    use carddata::MASS;
+
+    let parsed = MASS.parse(&CARD_MASS[2..]);
+    let indices: Vec<usize> = parsed.iter().map(|l| l.index).collect();
+
+    assert_eq!(vec![0, 2, 4, 6, 7], indices);
+    assert_eq!(Value::Str(" MASS  / ->1"), parsed[1].cells[1].value);
+    assert_eq!(Value::Blank, parsed[2].cells[1].value);
+  }
 }
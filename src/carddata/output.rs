@@ -0,0 +1,182 @@
+//! This modules holds the the global static output [`Card`](::card::Card)
+//! instances, i.e. time history and section force output requests.
+use card::cell::Cell::*;
+use card::field::*;
+use card::ges::GesType::*;
+use card::keyword::Keyword::*;
+use card::line::Line::*;
+use card::Card;
+
+const IDNOD: Field =
+  Field::new("IDNOD", "Node ID, nodes given by the GES if 0").default("0");
+
+pub static THNOD: Card = Card {
+  lines: &[
+    Cells(&[Kw, Integer(8), Integer(8)]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+  ],
+  fields: &[&[KEYWORD, IDNOD, IFRA], &[NAME, TITLE]],
+  ownfold: true,
+  keyword: Thnod,
+};
+
+/// The layout of [`THNOD`](::carddata::THNOD) used for decks with an
+/// `INPUTVERSION` before 2010, without a frame and a title line. It has not
+/// been checked against the manual of an older release or a deck written
+/// for one.
+pub static THNOD_2009: Card = Card {
+  lines: &[Cells(&[Kw, Integer(8)]), Ges(GesNode)],
+  fields: &[&[KEYWORD, IDNOD]],
+  ownfold: true,
+  keyword: Thnod,
+};
+
+pub static THLOC: Card = Card {
+  lines: &[
+    Cells(&[
+      Kw,
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Integer(8),
+    ]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDTHL", "Local time history ID"),
+      IDNOD,
+      Field::new("IDNOD1", "Node ID defining the local x axis"),
+      Field::new("IDNOD2", "Node ID defining the local xy plane"),
+      IFRA,
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Thloc,
+};
+
+/// A section force output. The first GES selects the nodes of the section,
+/// the second one the elements on one side of it.
+pub static SECFO: Card = Card {
+  lines: &[
+    Cells(&[
+      Kw,
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Integer(8),
+      Integer(8),
+    ]),
+    Cells(&[Fixed("NAME"), Str(76)]),
+    Ges(GesNode),
+    Ges(GesEle),
+  ],
+  fields: &[
+    &[
+      KEYWORD,
+      Field::new("IDSEC", "Section ID"),
+      Field::new("ITYP", "Type of the section").default("0"),
+      IFRA,
+      Field::new("IDNOD1", "Node ID of the section origin").default("0"),
+      Field::new("IDNOD2", "Node ID defining the section x axis").default("0"),
+      Field::new("IDNOD3", "Node ID defining the section xy plane")
+        .default("0"),
+    ],
+    &[NAME, TITLE],
+  ],
+  ownfold: true,
+  keyword: Secfo,
+};
+
+#[cfg(test)]
+mod tests {
+
+  const CARD_OUTPUT: [&str; 22] = [
+    "$#         IDNOD    IFRA",
+    "THNOD /     1000       0",
+    "$#                                                                         TITLE",
+    "NAME Impactor_COG",
+    "THNOD /        0       0",
+    "NAME Fixed end",
+    "        NOD        1       10       19       28       37       46",
+    "        END",
+    "$#         IDTHL   IDNOD  IDNOD1  IDNOD2    IFRA",
+    "THLOC /        1     671     691     692       0",
+    "NAME Local output",
+    "$#         IDSEC    ITYP    IFRA  IDNOD1  IDNOD2  IDNOD3",
+    "SECFO /        1       0       0       0       0       0",
+    "NAME Section 1",
+    "        NOD        1:9      118:131",
+    "        END",
+    "        PART        1",
+    "        END",
+    "SECFO /        2       0       0     671     691     692",
+    "NAME Section 2",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn fold_output() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![
+      (1, 3, Thnod),
+      (4, 7, Thnod),
+      (9, 10, Thloc),
+      (12, 17, Secfo),
+      (18, 19, Secfo),
+    ];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_OUTPUT);
+
+    assert_eq!(v, foldlist.into_vec());
+  }
+
+  const CARD_THNOD_2009: [&str; 7] = [
+    "INPUTVERSION 2009",
+    "$#         IDNOD",
+    "THNOD /        0",
+    "        NOD        1       10       19       28       37       46",
+    "        END",
+    "THNOD /     1000",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn thnod_layout_depends_on_version() {
+    use card::keyword::Keyword;
+    use card::keyword::Keyword::*;
+    use folds::FoldList;
+    use validation::validate;
+
+    let v: Vec<(u64, u64, Keyword)> = vec![(2, 4, Thnod)];
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&CARD_THNOD_2009);
+
+    assert_eq!(v, foldlist.into_vec());
+    assert!(validate(&CARD_THNOD_2009).is_empty());
+
+    let mut newer = CARD_THNOD_2009;
+    newer[0] = "INPUTVERSION 2011";
+    assert!(!validate(&newer).is_empty());
+  }
+
+  #[test]
+  fn validate_output() {
+    use validation::validate;
+
+    assert!(validate(&CARD_OUTPUT).is_empty());
+
+    let parsed = ::carddata::SECFO.parse(&CARD_OUTPUT[12..]);
+    assert_eq!(6, parsed.len());
+  }
+}
@@ -1,13 +1,14 @@
 //! This modules holds the the global static part [`Card`](::card::Card)
 //! instances.
 use card::cell::Cell::*;
+use card::field::*;
 use card::keyword::Keyword::*;
 use card::line::Conditional::*;
 use card::line::Line::*;
 use card::Card;
 
 macro_rules! part {
-  ($($e: expr),+; $k: expr) => {
+  ($($e: expr),+; $($f: expr),+; $k: expr) => {
     Card {
       lines: &[
         Provides(&[
@@ -24,50 +25,140 @@ macro_rules! part {
         Cells(&[Float(10), Float(10)]),
         Cells(&[Float(10), Float(10), Float(10)]),
         $( $e ),+ ,
-        Cells(&[Fixed("END_PART")]), 
+        Cells(&[Fixed("END_PART")]),
+      ],
+      fields: &[
+        &[KEYWORD, IDPRT, ATYPE, IDMAT, IDVAMAT, IDTHMAT, IDPMAT],
+        &[],
+        &[NAME, TITLE],
+        &[DTELIM, TSCALF],
+        &[TCONT, EPSINI, COULFRIC],
+        $( $f ),+ ,
+        &[END_PART],
       ],
       ownfold: true,
       keyword: $k
-    };
+    }
   };
 }
 
+const ATYPE: Field = Field::new("ATYPE", "Type of the elements of the part");
+const IDMAT: Field = Field::new("IDMAT", "Material ID");
+const IDVAMAT: Field =
+  Field::new("IDVAMAT", "Material ID for the VA solver").default("0");
+const IDTHMAT: Field =
+  Field::new("IDTHMAT", "Thermal material ID").default("0");
+const IDPMAT: Field =
+  Field::new("IDPMAT", "Material ID for the porous solver").default("0");
+const DTELIM: Field =
+  Field::new("DTELIM", "Time step for element elimination").unit("time");
+const TSCALF: Field = Field::new("TSCALF", "Time step scale factor");
+const TCONT: Field =
+  Field::new("TCONT", "Contact thickness").unit("length");
+const EPSINI: Field = Field::new("EPSINI", "Initial strain");
+const COULFRIC: Field = Field::new("COULFRIC", "Coulomb friction coefficient");
+const END_PART: Field = Field::new("", "End of the part");
+
+const RT1: &[Field] = &[
+  Field::new("RT1", "Type of the first material direction"),
+  Field::new("XDIR1", "X component of the first material direction"),
+  Field::new("YDIR1", "Y component of the first material direction"),
+  Field::new("ZDIR1", "Z component of the first material direction"),
+];
+const RT2: &[Field] = &[
+  Field::new("RT2", "Type of the second material direction"),
+  Field::new("XDIR2", "X component of the second material direction"),
+  Field::new("YDIR2", "Y component of the second material direction"),
+  Field::new("ZDIR2", "Z component of the second material direction"),
+];
+const H: Field = Field::new("H", "Thickness").unit("length");
+const NINT: Field = Field::new("NINT", "Number of integration points");
+const RDIST: Field = Field::new("RDIST", "Search distance").unit("length");
+const INEXT: Field = Field::new("INEXT", "Number of extra lines").default("0");
+const RSEAR: Field = Field::new("RSEAR", "Search radius").unit("length");
+
 // Part 3D
 
 pub static PARTSOLID: Card = part!(
   Cells(&[Integer(5), Float(10), Float(10), Float(10)]),
   Cells(&[Integer(5), Float(10), Float(10), Float(10)])
+  ;
+  RT1,
+  RT2
   ;PartSolid);
 
 pub static PARTCOS3D: Card = part!(
   Cells(&[Blank(10), Float(10), Float(10), Float(10), Float(10), Integer(10)])
+  ;
+  &[
+    BLANK,
+    Field::new("THK", "Thickness").unit("length"),
+    Field::new("XDIR1", "X component of the material direction"),
+    Field::new("YDIR1", "Y component of the material direction"),
+    Field::new("ZDIR1", "Z component of the material direction"),
+    Field::new("IMETH", "Method to compute the thickness direction"),
+  ]
   ;PartCos3d);
 
 pub static PARTBSHEL: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartBshel);
 
 pub static PARTTETRA: Card = part!(
   Cells(&[Integer(5), Blank(5), Float(10), Float(10), Float(10)]),
   Cells(&[Integer(5), Blank(5), Float(10), Float(10), Float(10)])
-  ; PartTetra);
+  ;
+  &[],
+  &[]
+  ;PartTetra);
 
 pub static PARTSPHEL: Card = part!(
   Provides(&[Float(10), Float(10), Float(10), Float(10), Integer(5), Integer(5),
              Float(10), Float(10), Integer(5)],
            Number(46..51)),
   Repeat(&[Integer(10), Float(10)], 1)
-  ; PartSphel);
+  ;
+  &[
+    Field::new("RATIO", "Ratio of smoothing length to particle distance"),
+    Field::new("Hmin", "Minimum smoothing length").unit("length"),
+    Field::new("Hmax", "Maximum smoothing length").unit("length"),
+    Field::new("ETA", "Viscosity factor"),
+    Field::new("INORM", "Normalisation flag"),
+    Field::new("NPAIR", "Number of material pairs"),
+    Field::new("ALPHAmg", "Artificial viscosity factor alpha"),
+    Field::new("BETAmg", "Artificial viscosity factor beta"),
+    Field::new("NMON", "Number of monitored particles"),
+  ],
+  &[]
+  ;PartSphel);
 
 // PART 2D
 
 pub static PARTTSHEL: Card = part!(
   Cells(&[Float(10), Integer(5)])
+  ;
+  &[H, NINT]
   ;PartTshel);
 
 pub static PARTSHELL: Card = part!(
   Cells(&[Float(10), Integer(5), Float(10), Integer(5)]),
-  Cells(&[Integer(5), Float(10), Float(10), Float(10), Float(10)])
+  Cells(&[Integer(5), Blank(5), Float(10), Float(10), Float(10)])
+  ;
+  &[
+    H,
+    NINT,
+    Field::new("OFFSET", "Offset of the midsurface").unit("length"),
+    Field::new("NTDOF", "Thickness degree of freedom flag"),
+  ],
+  &[
+    Field::new("ORT", "Type of the orthotropy direction"),
+    BLANK,
+    Field::new("XDIR", "X component of the orthotropy direction"),
+    Field::new("YDIR", "Y component of the orthotropy direction"),
+    Field::new("ZDIR", "Z component of the orthotropy direction"),
+  ]
   ;PartShell);
 
 pub static PARTMEMBR: Card = part!(
@@ -75,12 +166,17 @@ pub static PARTMEMBR: Card = part!(
           Float(10), Float(10), Float(10),]),
   Cells(&[Integer(5), Blank(5), Float(10), Float(10), Float(10), Float(10),
           Float(10), Float(10), Float(10),])
+  ;
+  &[],
+  &[]
   ;PartMembr);
 
 // PART 1D
 
 pub static PARTBAR: Card = part!(
   Cells(&[Float(10)])
+  ;
+  &[Field::new("A", "Cross section area").unit("length^2")]
   ;PartBar);
 
 pub static PARTBEAM: Card = part!(
@@ -93,65 +189,143 @@ pub static PARTBEAM: Card = part!(
   Provides(&[Integer(5), Integer(5), Float(10), Float(10), Float(10)],
              Number(6..11)),
   Repeat(&[Float(10), Float(10), Float(10)], 1)
+  ;
+  &[
+    Field::new("A", "Cross section area").unit("length^2"),
+    Field::new("Ashs", "Shear area in s direction").unit("length^2"),
+    Field::new("Is", "Moment of inertia about s").unit("length^4"),
+    Field::new("It", "Moment of inertia about t").unit("length^4"),
+    Field::new("Ir", "Torsional moment of inertia").unit("length^4"),
+    BLANK,
+    Field::new("ITPR", "Flag for the torsional properties"),
+    Field::new("Asht", "Shear area in t direction").unit("length^2"),
+  ],
+  &[
+    Field::new("Ist", "Product of inertia st").unit("length^4"),
+    Field::new("COGs", "Center of gravity offset in s").unit("length"),
+    Field::new("COGt", "Center of gravity offset in t").unit("length"),
+    BLANK,
+    Field::new("RT1RR1", "Released degrees of freedom at node 1"),
+    BLANK,
+    Field::new("RT2RR2", "Released degrees of freedom at node 2"),
+  ],
+  &[
+    Field::new("ALPHA1", "Alpha factor at node 1"),
+    Field::new("BETA1", "Beta factor at node 1"),
+    Field::new("GAMMA1", "Gamma factor at node 1"),
+    Field::new("KSI1", "Ksi factor at node 1"),
+    Field::new("ETA1", "Eta factor at node 1"),
+  ],
+  &[
+    Field::new("ALPHA2", "Alpha factor at node 2"),
+    Field::new("BETA2", "Beta factor at node 2"),
+    Field::new("GAMMA2", "Gamma factor at node 2"),
+    Field::new("KSI2", "Ksi factor at node 2"),
+    Field::new("ETA2", "Eta factor at node 2"),
+  ],
+  &[],
+  &[]
   ;PartBeam);
 
 pub static PARTSPRING: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartSpring);
 
 pub static PARTSPRGBM: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartSprgbm);
 
 pub static PARTMBSPR: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartMbspr);
 
 pub static PARTJOINT: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartJoint);
 
 pub static PARTKJOIN: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartKjoin);
 
 pub static PARTMTOJNT: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartMtojnt);
 
 pub static PARTMBKJN: Card = part!(
   Cells(&[Blank(0)])
+  ;
+  &[]
   ;PartMbkjn);
 
 pub static PARTTIED: Card = part!(
   Provides(&[Float(10), Blank(60), Integer(10)], Int(71..81,1)),
   Optional(&[Integer(5), Blank(5), Float(10), Float(10), Float(10), Float(10)],
            1)
+  ;
+  &[RDIST, BLANK, INEXT],
+  &[]
   ;PartTied);
 
 pub static PARTSLINK: Card = part!(
   Provides(&[Float(10), Blank(60), Integer(10)], Int(71..81,1)),
   Optional(&[Integer(5), Blank(5), Float(10), Float(10), Float(10), Float(10)],
            1)
+  ;
+  &[RDIST, BLANK, INEXT],
+  &[]
   ;PartSlink);
 
 pub static PARTELINK: Card = part!(
   Provides(&[Float(10), Float(10), Blank(50), Integer(10)], Int(71..81,1)),
   Optional(&[Integer(5), Blank(5), Float(10), Float(10), Float(10), Float(10)],
            1)
+  ;
+  &[],
+  &[]
   ;PartElink);
 
 pub static PARTLLINK: Card = part!(
   Cells(&[Float(10), Float(10), Float(10), Integer(10)])
+  ;
+  &[
+    RSEAR,
+    Field::new("DISPW", "Spotweld displacement at failure").unit("length"),
+    Field::new("WIDTH", "Width of the weld line").unit("length"),
+    Field::new("NGWDTH", "Number of elements across the width"),
+  ]
   ;PartLlink);
 
 pub static PARTPLINK: Card = part!(
   Cells(&[Float(10), Integer(10), Float(10), Integer(10), Float(10), Float(10),
           Integer(10)])
+  ;
+  &[
+    RSEAR,
+    Field::new("NLAYR", "Number of connected layers"),
+    Field::new("SPWLG", "Length of the spotweld").unit("length"),
+    Field::new("NGESP", "Number of elements per spotweld"),
+    Field::new("SPOTRA", "Radius of the spotweld").unit("length"),
+    Field::new("THETA", "Angle of the spotweld").unit("angle"),
+    Field::new("IRADBEN", "Flag for radial bending"),
+  ]
   ;PartPlink);
 
 pub static PARTGAP: Card = part!(
   Cells(&[Blank(10), Float(10)])
+  ;
+  &[BLANK, Field::new("d", "Gap distance").unit("length")]
   ;PartGap);
 
 #[cfg(test)]
@@ -159,7 +333,7 @@ mod tests {
   use card::keyword::Keyword;
   use card::keyword::Keyword::*;
 
-  const CARD_PARTSOLID: [&'static str; 22] = [
+  const CARD_PARTSOLID: [&str; 22] = [
     "$PART Type SOLID",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SOLID       1       0       0       0",
@@ -196,7 +370,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSOLID2: [&'static str; 17] = [
+  const CARD_PARTSOLID2: [&str; 17] = [
     "$PART Type SOLID",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SOLID       1       0       0       0",
@@ -227,7 +401,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTCOS3D: [&'static str; 13] = [
+  const CARD_PARTCOS3D: [&str; 13] = [
     "$PART Type COS3D",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   COS3D       1       0       0       0",
@@ -254,7 +428,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTBSHEL: [&'static str; 12] = [
+  const CARD_PARTBSHEL: [&str; 12] = [
     "$PART Type BSHEL",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   BSHEL       1       0       0       0",
@@ -280,7 +454,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTTETRA: [&'static str; 15] = [
+  const CARD_PARTTETRA: [&str; 15] = [
     "$PART Type TETRA",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   TETRA       1       0       0       0",
@@ -309,7 +483,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSPHEL: [&'static str; 13] = [
+  const CARD_PARTSPHEL: [&str; 13] = [
     "$PART Type SPHEL",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SPHEL       0       0       0       0",
@@ -336,7 +510,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSPHEL2: [&'static str; 16] = [
+  const CARD_PARTSPHEL2: [&str; 16] = [
     "$PART Type SPHEL",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SPHEL       1       0       0       0",
@@ -366,7 +540,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTTSHEL: [&'static str; 12] = [
+  const CARD_PARTTSHEL: [&str; 12] = [
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   TSHEL       1       0       0       0",
     "$#                                                                         TITLE",
@@ -393,7 +567,7 @@ mod tests {
   }
 
 
-  const CARD_PARTSHELL: [&'static str; 15] = [
+  const CARD_PARTSHELL: [&str; 15] = [
     "$PART Type SHELL",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SHELL       2       0       0       0",
@@ -422,7 +596,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTMEMBR: [&'static str; 17] = [
+  const CARD_PARTMEMBR: [&str; 17] = [
     "$PART Type MEMBR",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   MEMBR       0       2       0       0",
@@ -453,7 +627,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTBAR: [&'static str; 13] = [
+  const CARD_PARTBAR: [&str; 13] = [
     "$PART Type BAR",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1     BAR       1       0       0       0",
@@ -480,7 +654,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTBEAM: [&'static str; 20] = [
+  const CARD_PARTBEAM: [&str; 20] = [
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1    BEAM       1       0       0       0",
     "$#                                                                         TITLE",
@@ -514,7 +688,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTBEAM2: [&'static str; 23] = [
+  const CARD_PARTBEAM2: [&str; 23] = [
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1    BEAM       1       0       0       0",
     "$#                                                                         TITLE",
@@ -551,7 +725,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSPRING: [&'static str; 12] = [
+  const CARD_PARTSPRING: [&str; 12] = [
     "$PART Type SPRING",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1  SPRING       1       0       0       0",
@@ -577,7 +751,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSPRGBM: [&'static str; 10] = [
+  const CARD_PARTSPRGBM: [&str; 10] = [
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1  SPRGBM       1       0       0       0",
     "$#                                                                         TITLE",
@@ -601,7 +775,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTMBSPR: [&'static str; 9] = [
+  const CARD_PARTMBSPR: [&str; 9] = [
     "$PART Type MBSPR",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   MBSPR       1       0       0       0",
@@ -624,7 +798,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTJOINT: [&'static str; 12] = [
+  const CARD_PARTJOINT: [&str; 12] = [
     "$PART Type JOINT",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   JOINT       1       0       0       0",
@@ -649,7 +823,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTKJOIN: [&'static str; 6] = [
+  const CARD_PARTKJOIN: [&str; 6] = [
     "PART  /        1   KJOIN       1       0       0       0",
     "NAME PART_1                                                                     ",
     "                              ",
@@ -669,7 +843,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTMTOJNT: [&'static str; 13] = [
+  const CARD_PARTMTOJNT: [&str; 13] = [
   "$PART Type MTOJNT",
   "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
   "PART  /        1  MTOJNT       1       0       0       0",
@@ -696,7 +870,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTMBKJN: [&'static str; 12] = [
+  const CARD_PARTMBKJN: [&str; 12] = [
     "$PART Type MBKJN",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   MBKJN       1       0       0       0",
@@ -722,7 +896,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTTIED: [&'static str; 11] = [
+  const CARD_PARTTIED: [&str; 11] = [
     "PART  /        1    TIED       1       0       0       0",
     "$#                                                                         TITLE",
     "NAME PART_1                                                                     ",
@@ -747,7 +921,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTTIED2: [&'static str; 12] = [
+  const CARD_PARTTIED2: [&str; 12] = [
     "PART  /        1    TIED       1       0       0       0",
     "$#                                                                         TITLE",
     "NAME PART_1                                                                     ",
@@ -773,7 +947,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTSLINK: [&'static str; 13] = [
+  const CARD_PARTSLINK: [&str; 13] = [
     "$PART Type SLINK",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   SLINK       1       0       0       0",
@@ -800,7 +974,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTELINK: [&'static str; 11] = [
+  const CARD_PARTELINK: [&str; 11] = [
     "$PART Type ELINK",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   ELINK       1       0       0       0",
@@ -825,7 +999,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTLLINK: [&'static str; 12] = [
+  const CARD_PARTLLINK: [&str; 12] = [
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   LLINK       1       0       0       0",
     "$#                                                                         TITLE",
@@ -851,7 +1025,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTPLINK: [&'static str; 9] = [
+  const CARD_PARTPLINK: [&str; 9] = [
     "$PART Type PLINK",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1   PLINK       1       0       0       0",
@@ -874,7 +1048,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const CARD_PARTGAP: [&'static str; 13] = [
+  const CARD_PARTGAP: [&str; 13] = [
     "$PART Type GAP",
     "$#         IDPRT   ATYPE   IDMAT IDVAMAT IDTHMAT  IDPMAT",
     "PART  /        1     GAP       0       0       0       0",
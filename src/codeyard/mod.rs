@@ -3,6 +3,7 @@
 use std::str::Bytes;
 
 use card::keyword::Keyword;
+use schema;
 
 /// parse_str seems to largely
 /// dominate the benchmark for
@@ -64,7 +65,7 @@ pub fn parse_str3(s: &str) -> Option<Keyword> {
 /// create_card_data,
 /// this might be a faster alternative. Needs real benchmarks!
 #[inline]
-#[allow(dead_code)]
+#[allow(dead_code, clippy::assertions_on_constants)]
 pub fn parse_str4(s: &str) -> Option<Keyword> {
   use card::keyword::Keyword::*;
   use std::cmp;
@@ -97,6 +98,129 @@ pub fn parse_str4(s: &str) -> Option<Keyword> {
   None
 }
 
+/// The former implementation of
+/// [`Keyword::parse`](::card::keyword::Keyword::parse), matching slices of
+/// the line instead of 8-byte words. Kept to compare them in the benchmarks.
+#[inline]
+#[allow(dead_code)]
+pub fn parse_slices<T: AsRef<[u8]>>(s: &T) -> Option<Keyword> {
+  use card::keyword::Keyword::*;
+
+  let s = s.as_ref();
+  let len = s.len();
+
+  if len == 0 || len < 8 {
+    None
+  } else {
+    let start = &s[0..8];
+
+    match start {
+      // Node
+      b"NODE  / " => Some(Node),
+      b"CNODE / " => Some(Cnode),
+      b"MASS  / " => Some(Mass),
+      b"NSMAS / " => Some(Nsmas),
+      b"NSMAS2/ " => Some(Nsmas2),
+      // Element
+      b"SOLID / " => Some(Solid),
+      b"HEXA20/ " => Some(Hexa20),
+      b"PENT15/ " => Some(Pent15),
+      b"PENTA6/ " => Some(Penta6),
+      b"TETR10/ " => Some(Tetr10),
+      b"TETR4 / " => Some(Tetr4),
+      b"BSHEL / " => Some(Bshel),
+      b"TSHEL / " => Some(Tshel),
+      b"SHELL / " => Some(Shell),
+      b"SHEL6 / " => Some(Shel6),
+      b"SHEL8 / " => Some(Shel8),
+      b"MEMBR / " => Some(Membr),
+      b"BEAM  / " => Some(Beam),
+      b"SPRGBM/ " => Some(Sprgbm),
+      b"BAR   / " => Some(Bar),
+      b"SPRING/ " => Some(Spring),
+      b"JOINT / " => Some(Joint),
+      b"KJOIN / " => Some(Kjoin),
+      b"MTOJNT/ " => Some(Mtojnt),
+      b"SPHEL / " => Some(Sphel),
+      b"SPHELO/ " => Some(Sphelo),
+      b"GAP   / " => Some(Gap),
+      b"IMPMA / " => Some(Impma),
+      // Link
+      b"ELINK / " => Some(Elink),
+      b"LLINK / " => Some(Llink),
+      b"SLINK / " => Some(Slink),
+      b"PLINK / " => Some(Plink),
+      b"TIED  / " => Some(Tied),
+      // Material
+      b"MATER / " => Some(Mater),
+      // Boundary conditions
+      b"BOUNC / " => Some(Bounc),
+      b"DISPL / " => Some(Displ),
+      b"INVEL / " => Some(Invel),
+      // Constraints
+      b"RBODY / " => Some(Rbody),
+      // Groups
+      b"GROUP / " => Some(Group),
+      // Contact
+      b"CNTAC / " => Some(Cntac),
+      // Functions
+      b"FUNCT / " => Some(Funct),
+      // Frames
+      b"FRAME / " => Some(Frame),
+      // Output
+      b"THNOD / " => Some(Thnod),
+      b"THLOC / " => Some(Thloc),
+      b"SECFO / " => Some(Secfo),
+      // Control
+      b"INPUTVER" => Some(Inputversion),
+      b"ANALYSIS" => Some(Analysis),
+      b"SOLVER  " => Some(Solver),
+      b"UNIT    " => Some(Unit),
+      b"SIGNAL  " => Some(Signal),
+      b"TITLE / " => Some(Title),
+      b"INCLU / " => Some(Inclu),
+      b"RUNEND/ " => Some(Runend),
+      b"OCTRL / " => Some(Octrl),
+      b"CTRL  / " => Some(Ctrl),
+      b"PART  / " => {
+        if len < 24 {
+          None
+        } else {
+          let p = &s[16..24];
+
+          match p {
+            b"   SOLID" => Some(PartSolid),
+            b"   BSHEL" => Some(PartBshel),
+            b"   TETRA" => Some(PartTetra),
+            b"   SPHEL" => Some(PartSphel),
+            b"   COS3D" => Some(PartCos3d),
+            b"   TSHEL" => Some(PartTshel),
+            b"   SHELL" => Some(PartShell),
+            b"   MEMBR" => Some(PartMembr),
+            b"     BAR" => Some(PartBar),
+            b"    BEAM" => Some(PartBeam),
+            b"  SPRING" => Some(PartSpring),
+            b"  SPRGBM" => Some(PartSprgbm),
+            b"   MBSPR" => Some(PartMbspr),
+            b"   JOINT" => Some(PartJoint),
+            b"   KJOIN" => Some(PartKjoin),
+            b"  MTOJNT" => Some(PartMtojnt),
+            b"   MBKJN" => Some(PartMbkjn),
+            b"    TIED" => Some(PartTied),
+            b"   SLINK" => Some(PartSlink),
+            b"   ELINK" => Some(PartElink),
+            b"   LLINK" => Some(PartLlink),
+            b"   PLINK" => Some(PartPlink),
+            b"     GAP" => Some(PartGap),
+            _ => schema::keyword(s),
+          }
+        }
+      }
+      _ => schema::keyword(s),
+    }
+  }
+}
+
 /// Structure to hold fold data
 #[allow(dead_code)]
 pub struct Fold {
@@ -0,0 +1,572 @@
+//! This module provides a concrete syntax tree of a deck. A
+//! [`Deck`](::cst::Deck) consists of [`CardNode`](::cst::CardNode)s, which
+//! consist of the lines and GES blocks of the card, which in turn consist of
+//! cells. Every node knows its [`Span`](::cst::Span) in the buffer, and the
+//! comments directly above it.
+//!
+//! Unlike the skip functions of
+//! [`NoCommentIter`](::nocommentiter::NoCommentIter), the tree records which
+//! [`Line`](::card::line::Line) of the card every line of the buffer belongs
+//! to, the results of the conditionals, and which
+//! [`Case`](::card::line::Case) of a [`Switch`](::card::line::Line::Switch)
+//! or [`Select`](::card::line::Line::Select) a line was chosen by. Folds are
+//! derived from it, see [`add_folds`](::folds::FoldList::add_folds).
+//!
+//! The cells are the one level that is not stored in the tree. They are
+//! parsed on demand by [`LineNode::cells`](::cst::LineNode::cells), since
+//! parsing every cell would make building the tree too slow to derive the
+//! folds from it.
+use std::cmp::Ordering;
+use std::ops::{Index, Range};
+use std::ptr;
+
+use card::cell::{Cell, Value};
+use card::ges::GesType;
+use card::keyword::Keyword;
+use card::line::{Case, CondResult, Line};
+use card::Card;
+use nocommentiter::{is_comment, CommentLess, NoCommentIter};
+use skipresult::SkipResult;
+
+/// A position in the buffer. Line and column are zero-based, the column
+/// counts bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+  pub line: usize,
+  pub col: usize,
+}
+
+/// The part of the buffer from `start` up to `end` (exclusive)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+  pub start: Pos,
+  pub end: Pos,
+}
+
+impl Span {
+  /// The span of the columns `cols` of line `line`
+  fn cols(line: usize, cols: Range<usize>) -> Span {
+    Span {
+      start: Pos {
+        line,
+        col: cols.start,
+      },
+      end: Pos {
+        line,
+        col: cols.end,
+      },
+    }
+  }
+
+  /// The lines the span covers (end exclusive)
+  pub fn lines(&self) -> Range<usize> {
+    self.start.line..self.end.line + 1
+  }
+
+  /// Check if the span contains the given position
+  pub fn contains(&self, pos: Pos) -> bool {
+    self.start <= pos && pos < self.end
+  }
+}
+
+/// A line of the buffer that does not belong to a card
+#[derive(Debug, PartialEq)]
+pub enum Trivia {
+  /// A comment, i.e. a line starting with `$` or `#`
+  Comment(Span),
+  /// A line that is neither a comment nor part of a card, e.g. a line
+  /// following a card that already ended
+  Unknown(Span),
+}
+
+impl Trivia {
+  /// The span of the line
+  pub fn span(&self) -> &Span {
+    match *self {
+      Trivia::Comment(ref s) | Trivia::Unknown(ref s) => s,
+    }
+  }
+
+  /// Check if the line is a comment
+  pub fn is_comment(&self) -> bool {
+    matches!(*self, Trivia::Comment(_))
+  }
+}
+
+/// A cell of a line of a card, see
+/// [`ParsedCell`](::card::line::ParsedCell). The span covers the columns
+/// given by the definition of the cell, the line might end before that.
+#[derive(Debug, PartialEq)]
+pub struct CellNode<'a> {
+  pub cell: &'static Cell,
+  pub value: Value<'a>,
+  pub span: Span,
+}
+
+/// A line of a card. `text` is the content of the line, and `cond` holds
+/// the result of the conditional if `line` is a
+/// [`Provides`](::card::line::Line::Provides). If the line belongs to a
+/// [`Switch`](::card::line::Line::Switch) or a
+/// [`Select`](::card::line::Line::Select), `case` is the case its
+/// conditional chose (the innermost one, if they are nested). `comments` are
+/// the comments above the line, after the previous line of the card.
+#[derive(Debug, PartialEq)]
+pub struct LineNode<'a> {
+  pub line: &'static Line,
+  pub text: &'a [u8],
+  pub cond: Option<CondResult>,
+  pub case: Option<&'static Case>,
+  pub comments: Vec<Trivia>,
+  pub span: Span,
+}
+
+impl<'a> LineNode<'a> {
+  /// The cells of the line. They are only parsed on demand, so building the
+  /// tree stays cheap enough to derive the folds from it.
+  pub fn cells(&self) -> Vec<CellNode<'a>> {
+    let index = self.span.start.line;
+
+    self
+      .line
+      .parse(self.text)
+      .into_iter()
+      .map(|c| CellNode {
+        cell: c.cell,
+        value: c.value,
+        span: Span::cols(index, c.range),
+      })
+      .collect()
+  }
+}
+
+/// A General Entity Selection inside a card. `lines` are the spans of its
+/// lines, including the `END` line if there is one. `comments` are the
+/// comments above the GES and between its lines.
+#[derive(Debug, PartialEq)]
+pub struct GesNode {
+  pub ges: &'static GesType,
+  pub lines: Vec<Span>,
+  pub comments: Vec<Trivia>,
+  pub span: Span,
+}
+
+/// A child of a [`CardNode`](::cst::CardNode)
+#[derive(Debug, PartialEq)]
+pub enum CardChild<'a> {
+  Line(LineNode<'a>),
+  Ges(GesNode),
+}
+
+impl<'a> CardChild<'a> {
+  /// The span of the child
+  pub fn span(&self) -> &Span {
+    match *self {
+      CardChild::Line(ref l) => &l.span,
+      CardChild::Ges(ref g) => &g.span,
+    }
+  }
+}
+
+/// A card of the deck. `card` is the layout it was parsed with, which
+/// depends on `version`, the `INPUTVERSION` in effect at its first line.
+/// `comments` are the lines above the card, after the previous card.
+#[derive(Debug)]
+pub struct CardNode<'a> {
+  pub keyword: Keyword,
+  pub card: &'static Card,
+  pub version: Option<u32>,
+  pub comments: Vec<Trivia>,
+  pub children: Vec<CardChild<'a>>,
+  pub span: Span,
+}
+
+impl<'a> CardNode<'a> {
+  /// The child the given line (zero-based) belongs to
+  pub fn child_at(&self, line: usize) -> Option<&CardChild<'a>> {
+    self
+      .children
+      .iter()
+      .find(|c| c.span().lines().contains(&line))
+  }
+}
+
+/// The concrete syntax tree of a deck. `trailing` are the lines after the
+/// last card.
+#[derive(Debug)]
+pub struct Deck<'a> {
+  pub cards: Vec<CardNode<'a>>,
+  pub trailing: Vec<Trivia>,
+}
+
+impl<'a> Deck<'a> {
+  /// Parse the lines of a deck
+  pub fn parse<T: AsRef<[u8]>>(lines: &'a [T]) -> Deck<'a> {
+    let mut nodes = CardNodes::new(lines, lines.len(), 0, None);
+    let cards = nodes.by_ref().collect();
+
+    Deck {
+      cards,
+      trailing: nodes.trailing(),
+    }
+  }
+
+  /// The card the given line (zero-based) belongs to
+  pub fn card_at(&self, line: usize) -> Option<&CardNode<'a>> {
+    let idx = self
+      .cards
+      .binary_search_by(|c| {
+        let lines = c.span.lines();
+        if lines.end <= line {
+          Ordering::Less
+        } else if lines.start > line {
+          Ordering::Greater
+        } else {
+          Ordering::Equal
+        }
+      })
+      .ok()?;
+    Some(&self.cards[idx])
+  }
+}
+
+/// An iterator over the enumerated lines `range` of `lines`
+struct Indexed<'a, L: 'a + ?Sized> {
+  lines: &'a L,
+  range: Range<usize>,
+}
+
+impl<'a, L> Iterator for Indexed<'a, L>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: 'a,
+{
+  type Item = (usize, &'a L::Output);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let lines = self.lines;
+    self.range.next().map(|i| (i, &lines[i]))
+  }
+}
+
+/// An iterator over the [`CardNode`](::cst::CardNode)s of the first `len`
+/// lines of `lines`, which can be a slice or [`Lines`](::lines::Lines).
+/// Used to build a [`Deck`](::cst::Deck), or to parse only a part of the
+/// lines.
+pub struct CardNodes<'a, L>
+where
+  L: 'a + Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + Sized + 'a,
+{
+  len: usize,
+  li: NoCommentIter<Indexed<'a, L>>,
+  nextline: SkipResult<'a, L::Output>,
+  cursor: Cursor<'a, L>,
+}
+
+impl<'a, L> CardNodes<'a, L>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + Sized + 'a,
+{
+  /// Start parsing at line `start`, which needs to be the first line of a
+  /// card (or 0). `version` is the `INPUTVERSION` in effect at that line.
+  pub fn new(
+    lines: &'a L,
+    len: usize,
+    start: usize,
+    version: Option<u32>,
+  ) -> CardNodes<'a, L> {
+    let mut li = Indexed {
+      lines,
+      range: start..len,
+    }
+    .remove_comments()
+    .with_version(version);
+    let nextline = li.skip_to_next_keyword();
+
+    CardNodes {
+      len,
+      li,
+      nextline,
+      cursor: Cursor { lines, pos: start },
+    }
+  }
+
+  /// The `INPUTVERSION` after the cards returned so far
+  pub fn version(&self) -> Option<u32> {
+    self.li.version()
+  }
+
+  /// The lines after the last card. Only meaningful after the iterator
+  /// returned `None`.
+  pub fn trailing(mut self) -> Vec<Trivia> {
+    self.cursor.trivia(self.len)
+  }
+
+  /// Parse the card starting at line `start`, which has keyword `kw`
+  fn card_node(&mut self, kw: Keyword, start: usize) -> CardNode<'a> {
+    let card = self.li.card(&kw);
+    let version = self.li.version();
+    let comments = self.cursor.trivia(start);
+    let mut children = vec![];
+
+    let res = {
+      let cursor = &mut self.cursor;
+      self
+        .li
+        .walk_card(card, &self.nextline, &mut |i, _, cardline, case| {
+          cursor.push_child(&mut children, i, cardline, case)
+        })
+    };
+    self.nextline = res;
+
+    // The line with the keyword is always part of the card
+    let span = Span {
+      start: children[0].span().start,
+      end: children[children.len() - 1].span().end,
+    };
+
+    CardNode {
+      keyword: kw,
+      card,
+      version,
+      comments,
+      children,
+      span,
+    }
+  }
+}
+
+impl<'a, L> Iterator for CardNodes<'a, L>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + Sized + 'a,
+{
+  type Item = CardNode<'a>;
+
+  fn next(&mut self) -> Option<CardNode<'a>> {
+    loop {
+      let start = match self.nextline.nextline {
+        None => return None,
+        Some((i, _)) => i,
+      };
+
+      match self.nextline.nextline_kw {
+        // A line after a card that already ended
+        None => self.nextline = self.li.skip_to_next_keyword(),
+        Some(kw) => return Some(self.card_node(kw, start)),
+      }
+    }
+  }
+}
+
+/// The lines of the buffer, and the first line that is not yet part of a
+/// node
+struct Cursor<'a, L: 'a + ?Sized> {
+  lines: &'a L,
+  pos: usize,
+}
+
+impl<'a, L> Cursor<'a, L>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + 'a,
+{
+  /// The trivia on the lines from `pos` up to `end` (exclusive)
+  fn trivia(&mut self, end: usize) -> Vec<Trivia> {
+    let lines = self.lines;
+    let trivia = (self.pos..end)
+      .map(|i| {
+        let line = lines[i].as_ref();
+        let span = Span::cols(i, 0..line.len());
+        if is_comment(line) {
+          Trivia::Comment(span)
+        } else {
+          Trivia::Unknown(span)
+        }
+      })
+      .collect();
+
+    self.pos = self.pos.max(end);
+    trivia
+  }
+
+  /// Add the line `index`, which belongs to `line` of a card, to the
+  /// children of the card. `case` is the case `line` was chosen by, if any.
+  /// Lines of a GES are added to the GES of the previous line, if it did not
+  /// end yet.
+  fn push_child(
+    &mut self,
+    children: &mut Vec<CardChild<'a>>,
+    index: usize,
+    line: &'static Line,
+    case: Option<&'static Case>,
+  ) {
+    let lines = self.lines;
+    let comments = self.trivia(index);
+    let text = lines[index].as_ref();
+    let span = Span::cols(index, 0..text.len());
+    self.pos = index + 1;
+
+    match *line {
+      Line::Ges(ref ges) => {
+        let cont = match children.last() {
+          Some(CardChild::Ges(g)) => {
+            ptr::eq(g.ges, ges)
+              && !ges.ended_by(&lines[g.span.end.line].as_ref())
+          }
+          _ => false,
+        };
+
+        if let (true, Some(CardChild::Ges(g))) = (cont, children.last_mut()) {
+          g.lines.push(span);
+          g.comments.extend(comments);
+          g.span.end = span.end;
+        } else {
+          children.push(CardChild::Ges(GesNode {
+            ges,
+            lines: vec![span],
+            comments,
+            span,
+          }))
+        }
+      }
+      _ => {
+        let cond = match *line {
+          Line::Provides(_, ref c) => Some(c.evaluate(&text)),
+          _ => None,
+        };
+
+        children.push(CardChild::Line(LineNode {
+          line,
+          text,
+          cond,
+          case,
+          comments,
+          span,
+        }))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use card::cell::Value;
+  use card::keyword::Keyword::*;
+  use card::line::CondResult;
+  use cst::{CardChild, Deck, Pos, Span, Trivia};
+
+  const LINES: [&str; 13] = [
+    // 0
+    "$ Rigid body",
+    // 1
+    "RBODY /        1               3       0       0       0       0       0",
+    // 2
+    "NAME rigid body",
+    // 3
+    "$ Mass and inertia",
+    // 4
+    "       0     10.      1.      1.      1.",
+    // 5
+    "        NOD 1 2 3",
+    // 6
+    "$ More nodes",
+    // 7
+    "        NOD 4",
+    // 8
+    "        END",
+    // 9
+    "invalid line here",
+    // 10
+    "NODE  /        1              0.             0.5              0.",
+    // 11
+    "NODE  /        2              0.             0.5              0.",
+    // 12
+    "$ The end",
+  ];
+
+  fn line(line: usize, len: usize) -> Span {
+    Span {
+      start: Pos { line, col: 0 },
+      end: Pos { line, col: len },
+    }
+  }
+
+  #[test]
+  fn deck_can_be_parsed() {
+    let deck = Deck::parse(&LINES);
+    let kws: Vec<_> = deck.cards.iter().map(|c| c.keyword).collect();
+
+    assert_eq!(vec![Rbody, Node, Node], kws);
+    assert_eq!(vec![Trivia::Comment(line(12, 9))], deck.trailing);
+
+    let rbody = &deck.cards[0];
+    assert_eq!(vec![Trivia::Comment(line(0, 12))], rbody.comments);
+    assert_eq!(1..9, rbody.span.lines());
+    assert_eq!(4, rbody.children.len());
+
+    match rbody.children[0] {
+      CardChild::Line(ref l) => {
+        assert_eq!(Some(CondResult::Number(Some(3))), l.cond);
+        assert_eq!(None, l.case);
+      }
+      _ => panic!("Keyword line is not a line"),
+    }
+    match rbody.children[2] {
+      CardChild::Line(ref l) => {
+        assert_eq!(vec![Trivia::Comment(line(3, 18))], l.comments);
+        assert_eq!(4, l.span.start.line);
+        assert_eq!(None, l.cond);
+        assert_eq!(Some(3), l.case.map(|c| c.value));
+      }
+      _ => panic!("Case line is not a line"),
+    }
+    match rbody.children[3] {
+      CardChild::Ges(ref g) => {
+        assert_eq!(vec![line(5, 17), line(7, 13), line(8, 11)], g.lines);
+        assert_eq!(vec![Trivia::Comment(line(6, 12))], g.comments);
+        assert_eq!(5..9, g.span.lines());
+      }
+      _ => panic!("GES is not a GES"),
+    }
+
+    assert_eq!(vec![Trivia::Unknown(line(9, 17))], deck.cards[1].comments);
+    assert!(deck.cards[2].comments.is_empty());
+  }
+
+  #[test]
+  fn cells_have_spans() {
+    let deck = Deck::parse(&LINES);
+
+    let cells = match deck.cards[1].children[0] {
+      CardChild::Line(ref l) => l.cells(),
+      _ => panic!("NODE line is not a line"),
+    };
+
+    assert_eq!(5, cells.len());
+    assert_eq!(Value::Integer(1), cells[1].value);
+    assert_eq!(
+      Span {
+        start: Pos { line: 10, col: 8 },
+        end: Pos { line: 10, col: 16 },
+      },
+      cells[1].span
+    );
+  }
+
+  #[test]
+  fn nodes_can_be_found_by_line() {
+    let deck = Deck::parse(&LINES);
+
+    assert_eq!(Some(Rbody), deck.card_at(6).map(|c| c.keyword));
+    assert_eq!(Some(11), deck.card_at(11).map(|c| c.span.start.line));
+    assert!(deck.card_at(0).is_none());
+    assert!(deck.card_at(9).is_none());
+    assert!(deck.card_at(12).is_none());
+
+    let rbody = &deck.cards[0];
+    assert!(matches!(rbody.child_at(6), Some(CardChild::Ges(_))));
+    assert!(rbody.child_at(3).is_none());
+  }
+}
@@ -0,0 +1,129 @@
+//! This module provides [`curves`](::curve::curves) to read the points of all
+//! load curves (`FUNCT` cards) of a buffer, e.g. to inspect or plot them.
+use std::ptr;
+
+use card::cell::Value;
+use card::keyword::Keyword;
+use card::line::ParsedLine;
+use carddata::FUNCT;
+use nocommentiter::CommentLess;
+use skipresult::SkipResult;
+
+/// A load curve. `line` is the index of the line containing the keyword
+/// (0-based). The points are given as they are written in the card, without
+/// applying the scale factors and shifts of the keyword line.
+#[derive(Debug, PartialEq)]
+pub struct Curve {
+  pub id: i64,
+  pub line: usize,
+  pub title: String,
+  pub points: Vec<(f64, f64)>,
+}
+
+impl Curve {
+  /// Create a curve from the parsed lines of a `FUNCT` card. Returns `None`
+  /// if the card has no valid function ID. Points with missing or invalid
+  /// coordinates are skipped, and at most as many points as given on the
+  /// keyword line are read.
+  pub fn from_parsed(parsed: &[ParsedLine]) -> Option<Curve> {
+    let first = parsed.first()?;
+    let id = match first.cells.get(1).map(|c| &c.value) {
+      Some(Value::Integer(i)) => *i,
+      _ => return None,
+    };
+    let npts = match first.cells.get(2).map(|c| &c.value) {
+      Some(Value::Integer(n)) if *n > 0 => *n as usize,
+      _ => 0,
+    };
+
+    let mut title = String::new();
+    let mut points = Vec::with_capacity(npts);
+
+    for pl in &parsed[1..] {
+      if ptr::eq(pl.line, &FUNCT.lines[1]) {
+        if let Some(Value::Str(s)) = pl.cells.get(1).map(|c| &c.value) {
+          title = s.trim().to_owned();
+        }
+      } else if ptr::eq(pl.line, &FUNCT.lines[2]) {
+        for pair in pl.cells.chunks(2) {
+          if let [x, y] = pair {
+            if let (Value::Float(x), Value::Float(y)) = (&x.value, &y.value) {
+              points.push((*x, *y));
+            }
+          }
+        }
+      }
+    }
+    points.truncate(npts);
+
+    Some(Curve {
+      id,
+      line: first.index,
+      title,
+      points,
+    })
+  }
+}
+
+/// Read all load curves of `lines`, in order of occurence
+pub fn curves<T: AsRef<[u8]>>(lines: &[T]) -> Vec<Curve> {
+  let mut curves = vec![];
+  let mut li = lines.iter().enumerate().remove_comments();
+  let mut nextline = SkipResult::from(li.next());
+
+  while nextline.nextline.is_some() {
+    nextline = match nextline.nextline_kw {
+      None => SkipResult::from(li.next()),
+      Some(Keyword::Funct) => {
+        let (res, parsed) = li.parse_card(&nextline);
+        curves.extend(Curve::from_parsed(&parsed));
+        res
+      }
+      Some(_) => li.skip_fold(&nextline),
+    };
+  }
+
+  curves
+}
+
+#[cfg(test)]
+mod tests {
+  use curve::{curves, Curve};
+
+  const LINES: [&str; 14] = [
+    "NODE  /        1              0.             0.5              0.",
+    "NODE  /        2              0.             0.5              0.",
+    "$#         IDFUN    NPTS     SCALX     SCALY     SHIFX     SHIFY",
+    "FUNCT /        1       3        1.        1.        0.        0.",
+    "NAME Impactor velocity",
+    "                  0.                  0.                 0.1                 1.5",
+    "                 0.2                 2.5                 0.5                 3.5",
+    "FUNCT /        2       2",
+    "NAME Ramp",
+    "                  0.                  0.                  1.                 1.x",
+    "garbage",
+    "FUNCT /         ",
+    "NAME No ID",
+    "NODE  /        3              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn curves_can_be_read() {
+    let v = vec![
+      Curve {
+        id: 1,
+        line: 3,
+        title: "Impactor velocity".to_owned(),
+        points: vec![(0., 0.), (0.1, 1.5), (0.2, 2.5)],
+      },
+      Curve {
+        id: 2,
+        line: 7,
+        title: "Ramp".to_owned(),
+        points: vec![(0., 0.)],
+      },
+    ];
+
+    assert_eq!(v, curves(&LINES));
+  }
+}
@@ -2,6 +2,7 @@
 //! [`NeovimHandler`](::handler::NeovimHandler) to the main loop.
 use failure::Error;
 use std::fmt;
+use std::path::Path;
 use std::sync::mpsc;
 
 use neovim_lib::neovim::Neovim;
@@ -9,20 +10,24 @@ use neovim_lib::neovim_api::Buffer;
 use neovim_lib::neovim_api::NeovimApi;
 
 use folds::FoldList;
+use group;
+use header;
+use include::{self, Model};
 use lines::Lines;
 use neovim_ext::BufferExt;
+use validation;
 
 /// The event list the main loop reacts to
 pub enum Event {
   /// Neovim's answer after sending registering for buffer events.
-  /// `linedata` contains the buffers contents, without newlines. `more`
-  /// indicates if we need to expect another event of this type with more
-  /// lines, in case Neovim decided to split up the buffer (not yet
+  /// `linedata` contains the buffers contents as bytes, without newlines.
+  /// `more` indicates if we need to expect another event of this type with
+  /// more lines, in case Neovim decided to split up the buffer (not yet
   /// implemented).
   UpdatesStart {
     buf: Buffer,
     changedtick: u64,
-    linedata: Vec<String>,
+    linedata: Vec<Vec<u8>>,
     more: bool,
   },
   /// The update notification for a buffer change. Full lines only. Firstline
@@ -34,7 +39,7 @@ pub enum Event {
     changedtick: u64,
     firstline: u64,
     numreplaced: u64,
-    linedata: Vec<String>,
+    linedata: Vec<Vec<u8>>,
   },
   /// Update notification for a new `changedtick` without a buffer change.
   /// Used by undo/redo.
@@ -46,6 +51,19 @@ pub enum Event {
   UpdatesEnd { buf: Buffer },
   /// Recreate and resend the folds
   RefreshFolds,
+  /// Validate the buffer and all files it includes, and put the diagnostics
+  /// into the location list
+  Validate,
+  /// Edit the file included by the `INCLU` card on the given line
+  /// (zero-indexed)
+  GotoInclude { line: u64 },
+  /// Jump to the definition of the group referenced on the given line
+  /// (zero-indexed). If the line references several groups, the one at the
+  /// given column (zero-indexed) is used.
+  GotoGroup { line: u64, col: u64 },
+  /// Replace the `$#` header above the card line at the given line
+  /// (zero-indexed) by a correct one, or insert it if there is none
+  RegenerateHeader { line: u64 },
   /// This plugin should quit. Currently only sent by the user directly.
   Quit,
 }
@@ -58,11 +76,21 @@ impl Event {
   /// [buffer events](::neovim_ext::BufferExt::event_sub).
   /// It creates [`lines`](::lines::Lines) and a
   /// [`foldlist`](::folds::FoldList)  and updates them from the
-  /// events received. It calls
+  /// events received. The foldlist is [updated](::folds::FoldList::update)
+  /// only around the changed lines. It calls
   /// [`resend_all`](::folds::FoldList::resend_all) when
   /// the [`foldlist`](::folds::FoldList) was created, or the
   /// [`RefreshFolds`](../event/enum.Event.html#variant.RefreshFolds) event
-  /// was sent.
+  /// was sent. The [`Validate`](../event/enum.Event.html#variant.Validate)
+  /// event puts the result of [`Model::validate`](::include::Model::validate)
+  /// into the location list, and the
+  /// [`GotoInclude`](../event/enum.Event.html#variant.GotoInclude) event edits
+  /// the file included on the given line. The
+  /// [`GotoGroup`](../event/enum.Event.html#variant.GotoGroup) event jumps to
+  /// the definition of a group, which might be in an included file. The
+  /// [`RegenerateHeader`](../event/enum.Event.html#variant.RegenerateHeader)
+  /// event writes the [header](::header::header_at) of a card line into the
+  /// buffer.
   ///
   /// Sending the [`Quit`](../event/enum.Event.html#variant.Quit) event will
   /// exit the loop and return from the function.
@@ -91,12 +119,88 @@ impl Event {
           linedata,
           ..
         }) => {
-          lines.update(firstline, numreplaced, linedata);
-          foldlist.recreate_all(&lines)?;
+          let added = linedata.len() as u64;
+          lines.update(firstline, numreplaced, linedata)?;
+          foldlist.update(&lines, firstline, numreplaced, added)?;
         }
         Ok(RefreshFolds) => {
           foldlist.resend_all(&mut nvim)?;
         }
+        Ok(Validate) => {
+          let name = curbuf.get_name(&mut nvim)?;
+          let model = Model::new(name, lines.make_contiguous());
+          let diags = model.validate();
+          validation::send_diagnostics(&model, &diags, &mut nvim)?;
+        }
+        Ok(GotoInclude { line }) => {
+          let name = curbuf.get_name(&mut nvim)?;
+          match lines.get(line as usize).and_then(include::included_file) {
+            Some(f) => edit(&mut nvim, &include::resolve(name, f), 0)?,
+            None => echo(&mut nvim, "No INCLU card on this line")?,
+          }
+        }
+        Ok(GotoGroup { line, col }) => {
+          let refs = match lines.get(line as usize) {
+            Some(l) => group::line_refs(l, line as usize),
+            None => vec![],
+          };
+          let groupref = refs
+            .iter()
+            .find(|r| r.range.contains(&(col as usize)))
+            .or_else(|| refs.first());
+
+          match groupref {
+            None => echo(&mut nvim, "No group reference on this line")?,
+            Some(r) => {
+              let name = curbuf.get_name(&mut nvim)?;
+              let lines = lines.make_contiguous();
+              let local =
+                group::groups(lines).into_iter().find(|g| g.name == r.name);
+              // The included files are only read if the group is not defined
+              // in the buffer
+              let found = match local {
+                Some(g) => Some((None, g)),
+                None => {
+                  let model = Model::new(name, lines);
+                  model
+                    .find_group(&r.name)
+                    .map(|(f, g)| (Some(model.files[f].path.clone()), g))
+                }
+              };
+
+              match found {
+                Some((None, g)) => nvim.command(&format!("{}", g.line + 1))?,
+                Some((Some(path), g)) => edit(&mut nvim, &path, g.line)?,
+                None => echo(
+                  &mut nvim,
+                  &format!(
+                    "Group '{}' is not defined",
+                    String::from_utf8_lossy(&r.name)
+                  ),
+                )?,
+              }
+            }
+          }
+        }
+        Ok(RegenerateHeader { line }) => {
+          match header::header_at(&lines, line as usize) {
+            None => echo(&mut nvim, "No card line with named cells here")?,
+            Some((idx, h)) => {
+              let above = idx.checked_sub(1).map(|i| &lines[i]);
+              let start = match above {
+                Some(l) if header::is_header(l) => idx - 1,
+                _ => idx,
+              };
+              curbuf.set_lines(
+                &mut nvim,
+                start as u64,
+                idx as u64,
+                true,
+                vec![h],
+              )?;
+            }
+          }
+        }
         Ok(Quit) => {
           break;
         }
@@ -151,7 +255,33 @@ impl fmt::Debug for Event {
       }
       UpdatesEnd { .. } => write!(f, "UpdatesEnd"),
       RefreshFolds => write!(f, "RefreshFolds"),
+      Validate => write!(f, "Validate"),
+      GotoInclude { line } => write!(f, "GotoInclude{{ line: {} }}", line),
+      GotoGroup { line, col } => {
+        write!(f, "GotoGroup{{ line: {}, col: {} }}", line, col)
+      }
+      RegenerateHeader { line } => {
+        write!(f, "RegenerateHeader{{ line: {} }}", line)
+      }
       Quit => write!(f, "Quit"),
     }
   }
 }
+
+/// Edit the file at `path` and put the cursor on the given line (zero-indexed)
+fn edit(nvim: &mut Neovim, path: &Path, line: usize) -> Result<(), Error> {
+  let path = path.to_string_lossy().replace('\'', "''");
+  nvim.command(&format!(
+    "execute 'edit +{}' fnameescape('{}')",
+    line + 1,
+    path
+  ))?;
+  Ok(())
+}
+
+/// Show a message in the command line
+fn echo(nvim: &mut Neovim, msg: &str) -> Result<(), Error> {
+  let msg = msg.replace('\'', "''");
+  nvim.command(&format!("echo 'Nvimpam: {}'", msg))?;
+  Ok(())
+}
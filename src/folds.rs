@@ -14,6 +14,9 @@
 //!
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::ops::Index;
+use std::panic;
+use std::thread;
 
 use failure;
 use failure::Error;
@@ -23,7 +26,13 @@ use failure::ResultExt;
 use neovim_lib::{Neovim, NeovimApi};
 
 use card::keyword::Keyword;
-use nocommentiter::CommentLess;
+use cst::{CardNodes, Trivia};
+use lines::Lines;
+use nocommentiter::{is_comment, CommentLess};
+
+/// Decks with at least this many lines are parsed on several threads by
+/// [`add_folds`](::folds::FoldList::add_folds)
+pub const PARALLEL_MIN_LINES: usize = 100_000;
 
 /// Holds the fold data of the buffer. A fold has the following data:
 /// Linenumbers start, end (indexed from 1), and a
@@ -37,6 +46,10 @@ pub struct FoldList {
   /// lexicographically on [end, start].  Kept synchronous to Folds by the
   /// struct methods.
   folds_inv: BTreeMap<[u64; 2], Keyword>,
+  /// The `INPUTVERSION` in effect after each `INPUTVERSION` card, keyed by
+  /// the line of the card. Needed to continue parsing in the middle of the
+  /// buffer, see [`update`](::folds::FoldList::update).
+  versions: BTreeMap<u64, Option<u32>>,
 }
 
 impl FoldList {
@@ -46,6 +59,7 @@ impl FoldList {
     FoldList {
       folds: BTreeMap::new(),
       folds_inv: BTreeMap::new(),
+      versions: BTreeMap::new(),
     }
   }
 
@@ -53,6 +67,7 @@ impl FoldList {
   pub fn clear(&mut self) {
     self.folds.clear();
     self.folds_inv.clear();
+    self.versions.clear();
   }
 
   /// Insert a fold (start, end) into the FoldList. Returns an error if that
@@ -101,10 +116,120 @@ impl FoldList {
   }
 
   /// Remove all the entries from the FoldList, and iterate over lines to
-  /// populate it with new ones
-  pub fn recreate_all(&mut self, lines: &[String]) -> Result<(), Error> {
+  /// populate it with new ones, like
+  /// [`add_folds`](::folds::FoldList::add_folds) does.
+  pub fn recreate_all(&mut self, lines: &Lines) -> Result<(), Error> {
     self.clear();
-    self.add_folds(lines)
+    self.add_folds_indexed(lines, lines.len())
+  }
+
+  /// Update the FoldList after the lines `first..last` (zero-indexed, end
+  /// exclusive) of the buffer were replaced by `added` new lines. `lines`
+  /// are the lines after the change.
+  ///
+  /// Parsing starts again at the last card before `first`. If that card was
+  /// gathered into the fold before it, that fold is kept open. Parsing stops
+  /// at the first card after the new lines that is parsed with the same
+  /// `INPUTVERSION` as before, and either started a fold before the change,
+  /// or is gathered into the same fold as before. From there on, the old
+  /// folds are kept and only moved. The result is the same as the one of
+  /// [`recreate_all`](::folds::FoldList::recreate_all).
+  pub fn update(
+    &mut self,
+    lines: &Lines,
+    first: u64,
+    last: u64,
+    added: u64,
+  ) -> Result<(), Error> {
+    let delta = added as i64 - (last as i64 - first as i64);
+    let shift = |l: u64| (l as i64 + delta) as u64;
+    // The line a line of the old buffer has now, if it wasn't replaced
+    let to_new = |l: u64| match l {
+      l if l < first => Some(l),
+      l if l >= last => Some(shift(l)),
+      _ => None,
+    };
+
+    // Every keyword starts a card. The lines before `first` did not change,
+    // so the card at `start` is gathered if and only if it was before.
+    let start = (0..first)
+      .rev()
+      .find(|&i| Keyword::parse(&lines[i as usize]).is_some())
+      .unwrap_or(0);
+    let version = self
+      .versions
+      .range(..start)
+      .next_back()
+      .and_then(|(_, v)| *v);
+    let open = self
+      .folds
+      .range(..[start, 0])
+      .next_back()
+      .filter(|(k, _)| k[1] >= start)
+      .map(|(k, kw)| {
+        // Only comments are between a gathered card and the one before it
+        let end = (k[0]..start)
+          .rev()
+          .find(|&i| !is_comment(&lines[i as usize]))
+          .unwrap_or(k[0]);
+        (k[0], end, *kw)
+      });
+
+    // The folds from `start` on, or the open fold, are parsed again up to
+    // the sync point. Folds don't overlap, so these are exactly the ones
+    // ending after that.
+    let split = open.map_or(start, |(s, _, _)| s);
+    let old = self.folds.split_off(&[split, 0]);
+    let _ = self.folds_inv.split_off(&[split, 0]);
+    let oldversions = self.versions.split_off(&start);
+    // The old fold containing line `o`
+    let containing = |o: u64| {
+      old
+        .range(..=[o, u64::MAX])
+        .next_back()
+        .filter(|(k, _)| k[1] >= o)
+        .map(|(k, _)| *k)
+    };
+
+    let synced = self.add_folds_from(
+      lines,
+      lines.len(),
+      start as usize,
+      open,
+      version,
+      |t, v, into| {
+        t >= first + added && {
+          let o = (t as i64 - delta) as u64;
+          let oldversion = oldversions
+            .range(..o)
+            .next_back()
+            .map_or(version, |(_, ov)| *ov);
+
+          v == oldversion
+            && match (into, containing(o)) {
+              (None, Some(k)) => k[0] == o,
+              (Some(s), Some(k)) => k[0] < o && to_new(k[0]) == Some(s),
+              (_, None) => false,
+            }
+        }
+      },
+    )?;
+
+    if let Some(Stop { line: t, open }) = synced {
+      let o = (t as i64 - delta) as u64;
+
+      if let (Some((s, _, kw)), Some(k)) = (open, containing(o)) {
+        self.insert(s, shift(k[1]), kw)?;
+      }
+      for (k, kw) in old.range([o, 0]..) {
+        self.insert(shift(k[0]), shift(k[1]), *kw)?;
+      }
+      for (l, v) in oldversions.range(o..) {
+        self.versions.insert(shift(*l), *v);
+      }
+    }
+
+    Ok(())
   }
 
   /// Delete all folds in nvim, and create the ones from the FoldList
@@ -148,48 +273,319 @@ impl FoldList {
   /// definition of the card in the [carddata](::carddata) module, each card
   /// will be in an own fold, or several adjacent (modulo comments) cards will
   /// be subsumed into a fold.
-  pub fn add_folds<T: AsRef<str>>(&mut self, lines: &[T]) -> Result<(), Error> {
-    let mut li = lines.iter().enumerate().remove_comments();
-
-    let mut foldstart;
-    let mut foldend;
-    let mut foldkw;
-
-    let mut nextline = li.skip_to_next_keyword();
-
-    loop {
-      match nextline.nextline {
-        None => return Ok(()),
-        Some((i, _)) => {
-          match nextline.nextline_kw {
-            None => {
-              // Can this really happen?
-              nextline = li.skip_to_next_keyword();
-              continue;
-            }
-            Some(k) => foldkw = k,
-          };
+  ///
+  /// Decks with at least [`PARALLEL_MIN_LINES`](::folds::PARALLEL_MIN_LINES)
+  /// lines are parsed in chunks on several threads, see
+  /// [`add_folds_parallel`](::folds::FoldList::add_folds_parallel).
+  pub fn add_folds<T>(&mut self, lines: &[T]) -> Result<(), Error>
+  where
+    T: AsRef<[u8]> + Sync,
+  {
+    self.add_folds_indexed(lines, lines.len())
+  }
 
-          foldstart = i;
-          nextline = li.skip_fold(&nextline);
+  /// Like [`add_folds`](::folds::FoldList::add_folds), but for the first
+  /// `len` lines of `lines`, which can be a slice or
+  /// [`Lines`](::lines::Lines).
+  fn add_folds_indexed<L>(&mut self, lines: &L, len: usize) -> Result<(), Error>
+  where
+    L: Index<usize> + Sync + ?Sized,
+    L::Output: AsRef<[u8]> + Sized,
+  {
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    if threads > 1 && len >= PARALLEL_MIN_LINES {
+      self.add_folds_chunked(lines, len, threads)
+    } else {
+      self
+        .add_folds_from(lines, len, 0, None, None, |_, _, _| false)
+        .map(|_| ())
+    }
+  }
 
-          if let Some(j) = nextline.skip_end {
-            foldend = j;
-          } else {
-            // This only happens if the file ends directly after a GES
-            foldend = lines.len() - 1;
+  /// Like [`add_folds`](::folds::FoldList::add_folds), but split the lines
+  /// into `chunks` chunks starting at a keyword, and parse them on a thread
+  /// each. The result is the same as parsing all lines on one thread.
+  pub fn add_folds_parallel<T>(
+    &mut self,
+    lines: &[T],
+    chunks: usize,
+  ) -> Result<(), Error>
+  where
+    T: AsRef<[u8]> + Sync,
+  {
+    self.add_folds_chunked(lines, lines.len(), chunks)
+  }
+
+  /// Like [`add_folds_indexed`](::folds::FoldList::add_folds_indexed), but
+  /// split the lines into `chunks` chunks starting at a keyword, and parse
+  /// them on a thread each. The `INPUTVERSION` at the start of each chunk is
+  /// determined beforehand.
+  ///
+  /// The chunks are stitched together in order. A chunk is only used if the
+  /// previous chunk stopped parsing at its first line, and the
+  /// `INPUTVERSION` found so far is the one it was parsed with. If the card
+  /// at the start of the chunk belongs to the last fold of the previous
+  /// chunk, the two folds are merged. Otherwise (e.g. if the first line of
+  /// the chunk was not the start of a card), it is parsed again from where
+  /// the previous chunk stopped, so the result is always the same as
+  /// parsing all lines on one thread.
+  fn add_folds_chunked<L>(
+    &mut self,
+    lines: &L,
+    len: usize,
+    chunks: usize,
+  ) -> Result<(), Error>
+  where
+    L: Index<usize> + Sync + ?Sized,
+    L::Output: AsRef<[u8]> + Sized,
+  {
+    let starts = chunk_starts(lines, len, chunks);
+    let end = |k: usize| starts.get(k + 1).map(|&e| e as u64);
+
+    let found = in_parallel(starts.len(), |k| {
+      let range = starts[k]..end(k).map_or(len, |e| e as usize);
+      range
+        // Cheaper than `Keyword::parse`, and gives the same result
+        .filter(|&i| lines[i].as_ref().starts_with(b"INPUTVER"))
+        .collect::<Vec<usize>>()
+    });
+    let versions = start_versions(lines, len, &found);
+
+    let parsed = in_parallel(starts.len(), |k| {
+      let mut chunk = FoldList::new();
+      chunk
+        .add_folds_from(lines, len, starts[k], None, versions[k], |t, _, _| {
+          end(k).is_some_and(|e| t >= e)
+        })
+        .map(|stop| (chunk, stop))
+    });
+
+    let mut next = Some(Stop {
+      line: 0,
+      open: None,
+    });
+
+    for (k, res) in parsed.into_iter().enumerate() {
+      let Stop { line: pos, open } = match next {
+        Some(s) => s,
+        None => break,
+      };
+      let version = self.versions.values().next_back().and_then(|v| *v);
+      let (mut chunk, mut stop) = res?;
+
+      next = if pos == starts[k] as u64 && version == versions[k] {
+        // The first fold of the chunk continues the open fold. If the chunk
+        // stopped in that fold, it stays open.
+        if let Some((s, _, kw)) = open {
+          let first = chunk.folds.range([pos, 0]..=[pos, u64::MAX]).next();
+
+          match (first.map(|(key, _)| key[1]), stop.as_mut()) {
+            (Some(e), _) => {
+              let _ = chunk.folds.remove(&[pos, e]);
+              self.checked_insert(s, e, kw)?;
+            }
+            (
+              None,
+              Some(&mut Stop {
+                open: Some(ref mut o),
+                ..
+              }),
+            ) if o.0 == pos => o.0 = s,
+            (None, _) => self.checked_insert(s, pos, kw)?,
           }
         }
+        for (key, kw) in chunk.folds {
+          self.insert(key[0], key[1], kw)?;
+        }
+        self.versions.extend(chunk.versions);
+        stop
+      } else {
+        self.add_folds_from(
+          lines,
+          len,
+          pos as usize,
+          open,
+          version,
+          |t, _, _| end(k).is_some_and(|e| t >= e),
+        )?
+      };
+    }
+
+    Ok(())
+  }
+
+  /// Like [`add_folds`](::folds::FoldList::add_folds), but for the first
+  /// `len` lines of `lines`, which can be a slice or
+  /// [`Lines`](::lines::Lines), and start parsing at line `start`, which
+  /// needs to be the first line of a card (or 0). If the card is gathered
+  /// into the fold before it, that fold is passed as `open`.
+  /// `version` is the `INPUTVERSION` in effect at that line. `stop` is called
+  /// with the first line of every card, the `INPUTVERSION` in effect there,
+  /// and the start of the fold the card is gathered into, if any. If it
+  /// returns true, parsing stops before the card, see
+  /// [`Stop`](::folds::Stop).
+  ///
+  /// The folds are derived from the [`CardNode`](::cst::CardNode)s of the
+  /// lines. A card gets an own fold, or is gathered into the fold of the
+  /// card before it if they have the same keyword, `ownfold` is false, and
+  /// there are only comments between them.
+  fn add_folds_from<L, F>(
+    &mut self,
+    lines: &L,
+    len: usize,
+    start: usize,
+    open: Option<(u64, u64, Keyword)>,
+    version: Option<u32>,
+    mut stop: F,
+  ) -> Result<Option<Stop>, Error>
+  where
+    L: Index<usize> + ?Sized,
+    L::Output: AsRef<[u8]> + Sized,
+    F: FnMut(u64, Option<u32>, Option<u64>) -> bool,
+  {
+    let mut cards = CardNodes::new(lines, len, start, version);
+    let mut fold = open;
+    // The version in effect before the current card
+    let mut version = version;
+
+    while let Some(card) = cards.next() {
+      let first = card.span.start.line as u64;
+      let last = card.span.end.line as u64;
+
+      let into = match fold {
+        Some((s, _, kw))
+          if !card.card.ownfold
+            && kw == card.keyword
+            && card.comments.iter().all(Trivia::is_comment) =>
+        {
+          Some(s)
+        }
+        _ => None,
+      };
+
+      if into.is_none() {
+        if let Some((s, e, kw)) = fold.take() {
+          self.checked_insert(s, e, kw)?;
+        }
       }
-      self.checked_insert(foldstart as u64, foldend as u64, foldkw)?;
+      if stop(first, version, into) {
+        return Ok(Some(Stop {
+          line: first,
+          open: fold,
+        }));
+      }
+      fold = match fold {
+        Some((s, _, kw)) => Some((s, last, kw)),
+        None => Some((first, last, card.keyword)),
+      };
+
+      if card.keyword == Keyword::Inputversion {
+        self.versions.insert(first, cards.version());
+      }
+      version = cards.version();
+    }
+
+    if let Some((s, e, kw)) = fold {
+      self.checked_insert(s, e, kw)?;
+    }
+
+    Ok(None)
+  }
+}
+
+/// Where [`add_folds_from`](::folds::FoldList::add_folds_from) stopped:
+/// `line` is the first line of the card it stopped at. If that card would
+/// have been gathered into the fold before it, the fold is `open`, and was
+/// not added to the [`FoldList`](::folds::FoldList).
+struct Stop {
+  line: u64,
+  open: Option<(u64, u64, Keyword)>,
+}
+
+/// Split the first `len` lines of `lines` into (at most) `chunks` chunks of
+/// roughly the same size, and return the index of the first line of each
+/// chunk. A chunk starts at a line with a keyword, even if the card is
+/// gathered into the fold of the cards before it, so long runs of cards are
+/// split, too.
+fn chunk_starts<L>(lines: &L, len: usize, chunks: usize) -> Vec<usize>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + Sized,
+{
+  let mut starts = vec![0];
+
+  for k in 1..chunks {
+    let target = (k * len / chunks).max(starts[starts.len() - 1] + 1);
+    let start = (target..len).find(|&i| Keyword::parse(&lines[i]).is_some());
+
+    match start {
+      Some(i) => starts.push(i),
+      None => break,
     }
   }
+
+  starts
+}
+
+/// The `INPUTVERSION` in effect at the start of each chunk, given the lines
+/// starting with the `INPUTVERSION` keyword found in each chunk. Assumes
+/// that each of these lines starts a card. Only the first `len` lines of
+/// `lines` are used.
+fn start_versions<L>(
+  lines: &L,
+  len: usize,
+  found: &[Vec<usize>],
+) -> Vec<Option<u32>>
+where
+  L: Index<usize> + ?Sized,
+  L::Output: AsRef<[u8]> + Sized,
+{
+  let mut versions = Vec::with_capacity(found.len());
+  let mut version = None;
+
+  for f in found {
+    versions.push(version);
+
+    for &i in f {
+      let mut li = (i..len)
+        .map(|j| &lines[j])
+        .enumerate()
+        .remove_comments()
+        .with_version(version);
+      let nextline = li.skip_to_next_keyword();
+      let _ = li.skip_fold(&nextline);
+      version = li.version();
+    }
+  }
+
+  versions
+}
+
+/// Call `f` with `0..n` on a thread each, and collect the results in order
+fn in_parallel<R, F>(n: usize, f: F) -> Vec<R>
+where
+  R: Send,
+  F: Fn(usize) -> R + Sync,
+{
+  let f = &f;
+
+  thread::scope(|s| {
+    let handles: Vec<_> = (0..n).map(|k| s.spawn(move || f(k))).collect();
+
+    handles
+      .into_iter()
+      .map(|h| h.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+      .collect()
+  })
 }
 
 #[cfg(test)]
 mod tests {
+  use folds::FoldList;
+  use lines::Lines;
 
-  const LINES: [&'static str; 20] = [
+  const LINES: [&str; 20] = [
     /* 0 */
     "NODE  /        1              0.             0.5              0.",
     /* 1 */
@@ -235,7 +631,6 @@ mod tests {
   #[test]
   fn fold_general() {
     use card::keyword::Keyword::*;
-    use folds::FoldList;
 
     let mut v = vec![(0, 3, Node), (7, 15, Shell), (18, 19, Node)];
     let mut foldlist = FoldList::new();
@@ -258,7 +653,7 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
-  const LINES2: [&'static str; 24] = [
+  const LINES2: [&str; 24] = [
     // 0
     "NODE  /        1              0.             0.5              0.",
     // 1
@@ -312,7 +707,6 @@ mod tests {
   #[test]
   fn fold_general_gather() {
     use card::keyword::Keyword::*;
-    use folds::FoldList;
 
     let v = vec![
       (0, 3, Node),
@@ -326,4 +720,280 @@ mod tests {
     assert_eq!(v, foldlist.into_vec());
   }
 
+  const LINES3: [&str; 9] = [
+    // 0
+    "HEXA20/        1       1",
+    // 1
+    "                       1       2       3       4       5       6       7",
+    // 2
+    "HEXA20/        2       1",
+    // 3
+    "                       8       9      10      11      12      13      14",
+    // 4
+    "$Comment",
+    // 5
+    "NODE  /        1              0.             0.5              0.",
+    // 6
+    "#Comment",
+    // 7
+    "SHELL /     3129       1       1    2967    2971    2970",
+    // 8
+    "$Comment",
+  ];
+
+  #[test]
+  fn fold_gather_ends_with_last_card() {
+    use card::keyword::Keyword::*;
+
+    let mut foldlist = FoldList::new();
+    let _ = foldlist.add_folds(&LINES3);
+    assert_eq!(vec![(0, 3, Hexa20)], foldlist.into_vec());
+  }
+
+  #[test]
+  fn fold_latin1() {
+    use card::keyword::Keyword::*;
+
+    let lines = Lines::new(vec![
+      b"THNOD /     1000       0".to_vec(),
+      b"NAME Gel\xe4nde".to_vec(),
+      b"NODE  /        1              0.             0.5              0."
+        .to_vec(),
+      b"NODE  /        1              0.             0.5              0."
+        .to_vec(),
+    ]);
+    let mut foldlist = FoldList::new();
+    foldlist.recreate_all(&lines).unwrap();
+
+    assert_eq!(vec![(0, 1, Thnod), (2, 3, Node)], foldlist.into_vec());
+  }
+
+  /// Replace `lines[first..last]` by `added`, and check that updating the
+  /// folds gives the same result as parsing all lines again
+  fn check_update(
+    foldlist: &mut FoldList,
+    lines: &mut Lines,
+    first: usize,
+    last: usize,
+    added: Vec<Vec<u8>>,
+  ) {
+    let numadded = added.len() as u64;
+    lines.update(first as u64, last as u64, added).unwrap();
+    foldlist
+      .update(lines, first as u64, last as u64, numadded)
+      .unwrap();
+
+    let mut full = FoldList::new();
+    full.recreate_all(lines).unwrap();
+
+    assert_eq!(full.folds, foldlist.folds, "{}..{}", first, last);
+    assert_eq!(full.folds_inv, foldlist.folds_inv);
+    assert_eq!(full.versions, foldlist.versions);
+  }
+
+  #[test]
+  fn update_gives_same_folds() {
+    let file = ::std::fs::read_to_string("files/example.pc").unwrap();
+    let mut lines = Lines::new(file.lines().map(|l| l.into()).collect());
+    let mut foldlist = FoldList::new();
+    foldlist.recreate_all(&lines).unwrap();
+
+    // A simple linear congruential generator, for reproducible edits
+    let mut seed: u64 = 42;
+    let mut rand = |n: usize| {
+      seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+      (seed >> 33) as usize % n
+    };
+
+    for _ in 0..60 {
+      let first = rand(lines.len());
+      let last = (first + rand(4)).min(lines.len());
+      let added = match rand(4) {
+        0 => vec![],
+        1 => vec![b"garbage".to_vec()],
+        _ => {
+          let from = rand(lines.len() - 3);
+          (from..from + rand(3)).map(|i| lines[i].clone()).collect()
+        }
+      };
+      check_update(&mut foldlist, &mut lines, first, last, added);
+    }
+
+    let start =
+      vec![b"NODE  /        1              0.             0.5".to_vec()];
+    check_update(&mut foldlist, &mut lines, 0, 0, start);
+    let len = lines.len();
+    check_update(&mut foldlist, &mut lines, len - 2, len, vec![]);
+  }
+
+  #[test]
+  fn update_keeps_inputversion() {
+    let v: Vec<Vec<u8>> = [
+      "INPUTVERSION 2009",
+      "THNOD /        0",
+      "        NOD        1       10       19       28       37       46",
+      "        END",
+      "THNOD /        0",
+      "        NOD        1",
+      "        END",
+      "NODE  /        1              0.             0.5              0.",
+      "NODE  /        1              0.             0.5              0.",
+    ]
+    .iter()
+    .map(|l| l.as_bytes().to_vec())
+    .collect();
+    let mut lines = Lines::new(v);
+    let mut foldlist = FoldList::new();
+    foldlist.recreate_all(&lines).unwrap();
+
+    let added = vec![b"        NOD        2".to_vec()];
+    check_update(&mut foldlist, &mut lines, 5, 5, added);
+    let added = vec![b"INPUTVERSION 2011".to_vec()];
+    check_update(&mut foldlist, &mut lines, 0, 1, added);
+    let added = vec![b"$ comment".to_vec()];
+    check_update(&mut foldlist, &mut lines, 4, 4, added);
+  }
+
+  #[test]
+  fn update_reparses_after_inputversion_change() {
+    use card::keyword::Keyword::*;
+
+    let v: Vec<Vec<u8>> = [
+      "INPUTVERSION 2011",
+      "THNOD /     1000       0",
+      "NAME x",
+      "NODE  /        1              0.             0.5              0.",
+      "NODE  /        1              0.             0.5              0.",
+    ]
+    .iter()
+    .map(|l| l.as_bytes().to_vec())
+    .collect();
+    let mut lines = Lines::new(v);
+    let mut foldlist = FoldList::new();
+    foldlist.recreate_all(&lines).unwrap();
+
+    let added = vec![b"INPUTVERSION 2009".to_vec()];
+    check_update(&mut foldlist, &mut lines, 0, 1, added);
+    assert_eq!(vec![(3, 4, Node)], foldlist.into_vec());
+  }
+
+  #[test]
+  fn update_inside_gathered_folds() {
+    let node =
+      "NODE  /        1              0.             0.5              0.";
+    let shell = "SHELL /     3129       1       1    2967    2971    2970";
+    let mut v: Vec<Vec<u8>> = vec![];
+    for i in 0..40 {
+      match i % 13 {
+        5 => v.push(b"$ comment".to_vec()),
+        11 => v.push(shell.as_bytes().to_vec()),
+        _ => {}
+      }
+      v.push(node.as_bytes().to_vec());
+    }
+    let mut lines = Lines::new(v);
+    let mut foldlist = FoldList::new();
+    foldlist.recreate_all(&lines).unwrap();
+
+    let edits: [(usize, usize, &[&str]); 9] = [
+      (20, 21, &[node]),
+      (20, 21, &[shell]),
+      (20, 21, &[node]),
+      (3, 3, &["$ comment", node]),
+      (8, 8, &["invalid line here"]),
+      (8, 9, &[]),
+      (25, 28, &[]),
+      (1, 1, &[shell, node]),
+      (0, 1, &[]),
+    ];
+    for &(first, last, added) in &edits {
+      let added = added.iter().map(|l| l.as_bytes().to_vec()).collect();
+      check_update(&mut foldlist, &mut lines, first, last, added);
+    }
+  }
+
+  /// Check that parsing `lines` in `chunks` chunks gives the same result as
+  /// parsing them on one thread
+  fn check_parallel(lines: &[String], chunks: usize) {
+    let mut sequential = FoldList::new();
+    sequential
+      .add_folds_from(lines, lines.len(), 0, None, None, |_, _, _| false)
+      .unwrap();
+
+    let mut parallel = FoldList::new();
+    parallel.add_folds_parallel(lines, chunks).unwrap();
+
+    assert_eq!(sequential.folds, parallel.folds, "{} chunks", chunks);
+    assert_eq!(sequential.folds_inv, parallel.folds_inv);
+    assert_eq!(sequential.versions, parallel.versions);
+  }
+
+  #[test]
+  fn parallel_gives_same_folds() {
+    let file = ::std::fs::read_to_string("files/example.pc").unwrap();
+    let mut lines: Vec<String> = file.lines().map(|l| l.to_owned()).collect();
+
+    for chunks in &[1, 2, 3, 8, 50, 1000, lines.len() + 10] {
+      check_parallel(&lines, *chunks);
+    }
+
+    let thnod = [
+      "THNOD /        0",
+      "        NOD        1       10       19       28       37       46",
+      "        END",
+    ];
+    let mut versioned = vec!["INPUTVERSION 2009".to_owned()];
+    for _ in 0..20 {
+      versioned.extend(thnod.iter().map(|l| l.to_string()));
+      versioned.extend(lines[..40].iter().cloned());
+    }
+    versioned.push("INPUTVERSION 2011".to_owned());
+    versioned.extend(thnod.iter().map(|l| l.to_string()));
+    lines.splice(0..0, versioned);
+
+    for chunks in &[2, 7, 100] {
+      check_parallel(&lines, *chunks);
+    }
+  }
+
+  #[test]
+  fn parallel_merges_gathered_folds() {
+    let node =
+      "NODE  /        1              0.             0.5              0.";
+    let mut lines: Vec<String> = vec![];
+
+    for i in 0..500 {
+      match i % 97 {
+        0 => lines.push("$ comment".to_owned()),
+        50 => lines.push("invalid line here".to_owned()),
+        60 => lines.push("SHELL /     3129       1       1    2967".to_owned()),
+        _ => {}
+      }
+      lines.push(node.to_owned());
+    }
+
+    for chunks in &[2, 3, 7, 50, 499, 1000] {
+      check_parallel(&lines, *chunks);
+    }
+  }
+
+  #[test]
+  fn chunks_start_at_cards() {
+    use folds::chunk_starts;
+
+    let lines = [
+      "NODE  /        1              0.             0.5              0.",
+      "NODE  /        2              0.             0.5              0.",
+      "$ comment",
+      "NODE  /        3              0.             0.5              0.",
+      "SHELL /     3129       1       1    2967    2971    2970",
+      "NODE  /        4              0.             0.5              0.",
+    ];
+
+    assert_eq!(vec![0, 3], chunk_starts(&lines, lines.len(), 2));
+    assert_eq!(vec![0, 3, 4], chunk_starts(&lines, lines.len(), 3));
+    assert_eq!(vec![0, 1, 3, 4, 5], chunk_starts(&lines, lines.len(), 6));
+  }
 }
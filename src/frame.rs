@@ -0,0 +1,370 @@
+//! This module provides the local coordinate frames of a deck, defined by
+//! `FRAME` cards, and the [`Transformation`](::frame::Transformation) between
+//! the global frame and a local one.
+use std::collections::HashMap;
+
+use card::cell::Value;
+use card::keyword::Keyword;
+use card::line::ParsedLine;
+use nocommentiter::CommentLess;
+use skipresult::SkipResult;
+
+/// The definition of a local frame, as given on a `FRAME` card
+#[derive(Debug, PartialEq)]
+pub enum FrameDef {
+  /// The origin, the direction of the x axis and a direction in the xy plane
+  Vectors {
+    origin: [f64; 3],
+    xaxis: [f64; 3],
+    xyplane: [f64; 3],
+  },
+  /// The node IDs of the origin, a node on the x axis and a node in the xy
+  /// plane
+  Nodes([i64; 3]),
+}
+
+/// A local frame. `line` is the index of the line containing the keyword
+/// (0-based).
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+  pub id: i64,
+  pub line: usize,
+  pub title: String,
+  pub definition: FrameDef,
+}
+
+/// The transformation from the global frame to a local frame. The rows of
+/// `rotation` are the unit vectors of the local axes, given in global
+/// coordinates.
+#[derive(Debug, PartialEq)]
+pub struct Transformation {
+  pub origin: [f64; 3],
+  pub rotation: [[f64; 3]; 3],
+}
+
+impl Transformation {
+  /// Create the transformation to the frame with the given origin, x axis and
+  /// a direction in the xy plane. Returns `None` if the x axis is zero, or
+  /// parallel to the direction in the xy plane.
+  pub fn new(
+    origin: [f64; 3],
+    xaxis: [f64; 3],
+    xyplane: [f64; 3],
+  ) -> Option<Transformation> {
+    let ex = normalize(xaxis)?;
+    let ez = normalize(cross(xaxis, xyplane))?;
+    let ey = cross(ez, ex);
+
+    Some(Transformation {
+      origin,
+      rotation: [ex, ey, ez],
+    })
+  }
+
+  /// The homogeneous 4x4 matrix transforming global coordinates into local
+  /// ones
+  pub fn matrix(&self) -> [[f64; 4]; 4] {
+    let shift = self.direction_to_local(self.origin);
+    let r = &self.rotation;
+
+    [
+      [r[0][0], r[0][1], r[0][2], -shift[0]],
+      [r[1][0], r[1][1], r[1][2], -shift[1]],
+      [r[2][0], r[2][1], r[2][2], -shift[2]],
+      [0., 0., 0., 1.],
+    ]
+  }
+
+  /// Express the point `p`, given in global coordinates, in the local frame
+  pub fn to_local(&self, p: [f64; 3]) -> [f64; 3] {
+    self.direction_to_local(sub(p, self.origin))
+  }
+
+  /// Express the point `p`, given in local coordinates, in the global frame
+  pub fn to_global(&self, p: [f64; 3]) -> [f64; 3] {
+    let d = self.direction_to_global(p);
+    [
+      d[0] + self.origin[0],
+      d[1] + self.origin[1],
+      d[2] + self.origin[2],
+    ]
+  }
+
+  /// Express the direction `d`, given in global coordinates, in the local
+  /// frame
+  pub fn direction_to_local(&self, d: [f64; 3]) -> [f64; 3] {
+    let r = &self.rotation;
+    [dot(r[0], d), dot(r[1], d), dot(r[2], d)]
+  }
+
+  /// Express the direction `d`, given in local coordinates, in the global
+  /// frame
+  pub fn direction_to_global(&self, d: [f64; 3]) -> [f64; 3] {
+    let r = &self.rotation;
+    let mut g = [0.; 3];
+
+    for (i, gi) in g.iter_mut().enumerate() {
+      *gi = r[0][i] * d[0] + r[1][i] * d[1] + r[2][i] * d[2];
+    }
+    g
+  }
+}
+
+impl Frame {
+  /// Create a frame from the parsed lines of a `FRAME` card. Returns `None`
+  /// if the card has no valid frame ID, an unknown definition type, or
+  /// the lines of the definition are missing or invalid. Blank coordinates
+  /// are read as 0.
+  pub fn from_parsed(parsed: &[ParsedLine]) -> Option<Frame> {
+    let first = parsed.first()?;
+    let id = match first.cells.get(1).map(|c| &c.value) {
+      Some(Value::Integer(i)) => *i,
+      _ => return None,
+    };
+    let ityp = match first.cells.get(2).map(|c| &c.value) {
+      Some(Value::Integer(i)) => *i,
+      Some(Value::Blank) | None => 0,
+      _ => return None,
+    };
+
+    let mut title = String::new();
+    let mut definition = vec![];
+
+    for pl in &parsed[1..] {
+      match pl.cells.first().map(|c| &c.value) {
+        Some(Value::Fixed("NAME")) => {
+          if let Some(Value::Str(s)) = pl.cells.get(1).map(|c| &c.value) {
+            title = s.trim().to_owned();
+          }
+        }
+        Some(_) => definition.push(pl),
+        None => {}
+      }
+    }
+
+    let definition = match (ityp, definition.as_slice()) {
+      (0, [o, x, xy]) => FrameDef::Vectors {
+        origin: floats(o)?,
+        xaxis: floats(x)?,
+        xyplane: floats(xy)?,
+      },
+      (1, [n]) => FrameDef::Nodes(integers(n)?),
+      _ => return None,
+    };
+
+    Some(Frame {
+      id,
+      line: first.index,
+      title,
+      definition,
+    })
+  }
+
+  /// The transformation to this frame. `nodes` are the node coordinates,
+  /// needed if the frame is defined by nodes. Returns `None` if a node is
+  /// missing, or the definition is degenerate.
+  pub fn transformation(
+    &self,
+    nodes: &HashMap<i64, [f64; 3]>,
+  ) -> Option<Transformation> {
+    match self.definition {
+      FrameDef::Vectors {
+        origin,
+        xaxis,
+        xyplane,
+      } => Transformation::new(origin, xaxis, xyplane),
+      FrameDef::Nodes([n1, n2, n3]) => {
+        let origin = *nodes.get(&n1)?;
+        let xaxis = sub(*nodes.get(&n2)?, origin);
+        let xyplane = sub(*nodes.get(&n3)?, origin);
+
+        Transformation::new(origin, xaxis, xyplane)
+      }
+    }
+  }
+}
+
+/// Read all frames of `lines`, in order of occurence
+pub fn frames<T: AsRef<[u8]>>(lines: &[T]) -> Vec<Frame> {
+  let mut frames = vec![];
+  let mut li = lines.iter().enumerate().remove_comments();
+  let mut nextline = SkipResult::from(li.next());
+
+  while nextline.nextline.is_some() {
+    nextline = match nextline.nextline_kw {
+      None => SkipResult::from(li.next()),
+      Some(Keyword::Frame) => {
+        let (res, parsed) = li.parse_card(&nextline);
+        frames.extend(Frame::from_parsed(&parsed));
+        res
+      }
+      Some(_) => li.skip_fold(&nextline),
+    };
+  }
+
+  frames
+}
+
+/// The coordinates of all nodes in `lines`, by node ID. Nodes with invalid
+/// coordinates are skipped, blank coordinates are read as 0. If a node is
+/// defined more than once, the last definition is used.
+pub fn node_coordinates<T: AsRef<[u8]>>(lines: &[T]) -> HashMap<i64, [f64; 3]> {
+  let mut nodes = HashMap::new();
+  let mut li = lines.iter().enumerate().remove_comments();
+  let mut nextline = SkipResult::from(li.next());
+
+  while nextline.nextline.is_some() {
+    nextline = match nextline.nextline_kw {
+      None => SkipResult::from(li.next()),
+      Some(Keyword::Node) => {
+        let (res, parsed) = li.parse_card(&nextline);
+        if let Some(pl) = parsed.first() {
+          let id = match pl.cells.get(1).map(|c| &c.value) {
+            Some(Value::Integer(i)) => Some(*i),
+            _ => None,
+          };
+          let coords = pl.cells.get(2..5).and_then(|c| {
+            Some([
+              float(&c[0].value)?,
+              float(&c[1].value)?,
+              float(&c[2].value)?,
+            ])
+          });
+
+          if let (Some(id), Some(coords)) = (id, coords) {
+            nodes.insert(id, coords);
+          }
+        }
+        res
+      }
+      Some(_) => li.skip_fold(&nextline),
+    };
+  }
+
+  nodes
+}
+
+/// A float value, blanks are read as 0
+fn float(v: &Value) -> Option<f64> {
+  match *v {
+    Value::Float(f) => Some(f),
+    Value::Blank => Some(0.),
+    _ => None,
+  }
+}
+
+fn floats(pl: &ParsedLine) -> Option<[f64; 3]> {
+  match pl.cells.as_slice() {
+    [a, b, c] => Some([float(&a.value)?, float(&b.value)?, float(&c.value)?]),
+    _ => None,
+  }
+}
+
+fn integers(pl: &ParsedLine) -> Option<[i64; 3]> {
+  let mut v = [0; 3];
+
+  if pl.cells.len() != 3 {
+    return None;
+  }
+  for (i, c) in pl.cells.iter().enumerate() {
+    match c.value {
+      Value::Integer(n) => v[i] = n,
+      _ => return None,
+    }
+  }
+  Some(v)
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+  [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+  a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+  [
+    a[1] * b[2] - a[2] * b[1],
+    a[2] * b[0] - a[0] * b[2],
+    a[0] * b[1] - a[1] * b[0],
+  ]
+}
+
+fn normalize(a: [f64; 3]) -> Option<[f64; 3]> {
+  let len = dot(a, a).sqrt();
+
+  if len < 1e-12 {
+    None
+  } else {
+    Some([a[0] / len, a[1] / len, a[2] / len])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use frame::{frames, node_coordinates, FrameDef, Transformation};
+
+  const LINES: [&str; 17] = [
+    "NODE  /        1              1.              1.              0.",
+    "NODE  /        2              1.              2.              0.",
+    "NODE  /        3              0.              1.              0.",
+    "$#         IDFRA    ITYP",
+    "FRAME /        1       0",
+    "NAME Rotated",
+    "              0.              0.              0.",
+    "              0.              1.              0.",
+    "             -1.              0.              0.",
+    "FRAME /        2       1",
+    "NAME Moving",
+    "       1       2       3",
+    "FRAME /        3       1",
+    "NAME Missing node",
+    "       1       2       4",
+    "FRAME /         ",
+    "NAME No ID",
+  ];
+
+  fn assert_close(a: [f64; 3], b: [f64; 3]) {
+    for i in 0..3 {
+      assert!((a[i] - b[i]).abs() < 1e-12, "{:?} != {:?}", a, b);
+    }
+  }
+
+  #[test]
+  fn frames_can_be_read() {
+    let frames = frames(&LINES);
+    let ids: Vec<(i64, usize)> =
+      frames.iter().map(|f| (f.id, f.line)).collect();
+
+    assert_eq!(vec![(1, 4), (2, 9), (3, 12)], ids);
+    assert_eq!("Moving", frames[1].title);
+    assert_eq!(FrameDef::Nodes([1, 2, 3]), frames[1].definition);
+  }
+
+  #[test]
+  fn frames_transform_coordinates() {
+    let frames = frames(&LINES);
+    let nodes = node_coordinates(&LINES);
+
+    assert_eq!(3, nodes.len());
+
+    // x axis along global y, y axis along global -x
+    let t = frames[0].transformation(&nodes).unwrap();
+    assert_close([2., -1., 3.], t.to_local([1., 2., 3.]));
+    assert_close([1., 2., 3.], t.to_global([2., -1., 3.]));
+
+    // same orientation, but the origin is node 1
+    let t = frames[1].transformation(&nodes).unwrap();
+    assert_close([1., 0., 3.], t.to_local([1., 2., 3.]));
+    assert_close([0., 1., 0.], t.direction_to_global([1., 0., 0.]));
+    let m = t.matrix();
+    assert_close([m[0][3], m[1][3], m[2][3]], [-1., 1., 0.]);
+
+    assert_eq!(None, frames[2].transformation(&nodes));
+    assert_eq!(
+      None,
+      Transformation::new([0.; 3], [1., 0., 0.], [2., 0., 0.])
+    );
+  }
+}
@@ -0,0 +1,181 @@
+//! This module provides the named groups of a deck. Groups are defined by
+//! `GROUP` cards, the name is given on their title line. A GES references
+//! groups by their name in single quotes, e.g. `GRP 'hausbau'`, `OGRP` or
+//! `DELGRP`.
+use std::ops::Range;
+
+use card::keyword::Keyword;
+use nocommentiter::CommentLess;
+use skipresult::SkipResult;
+
+/// A group, defined by the `GROUP` card starting on `line` (0-based). The
+/// name is kept as bytes, like the lines, so names that are not valid UTF-8
+/// can be matched with their references.
+#[derive(Debug, PartialEq)]
+pub struct Group {
+  pub name: Vec<u8>,
+  pub line: usize,
+}
+
+/// A reference to a group in a GES. `range` are the byte columns of the
+/// name, including the quotes (0-based, end exclusive).
+#[derive(Debug, PartialEq)]
+pub struct GroupRef {
+  pub name: Vec<u8>,
+  pub line: usize,
+  pub range: Range<usize>,
+}
+
+/// Read all groups defined in `lines`, in order of occurence. Groups without
+/// a name are skipped.
+pub fn groups<T: AsRef<[u8]>>(lines: &[T]) -> Vec<Group> {
+  let mut groups = vec![];
+  let mut li = lines.iter().enumerate().remove_comments();
+  let mut nextline = SkipResult::from(li.next());
+
+  while let Some((i, _)) = nextline.nextline {
+    nextline = match nextline.nextline_kw {
+      None => SkipResult::from(li.next()),
+      Some(Keyword::Group) => {
+        let (res, parsed) = li.parse_card(&nextline);
+        let name = parsed.get(1).and_then(|p| {
+          let line = lines[p.index].as_ref();
+          let range = &p.cells.get(1)?.range;
+          line.get(range.start..range.end.min(line.len()))
+        });
+
+        match name.map(|n| n.trim_ascii()) {
+          Some(name) if !name.is_empty() => groups.push(Group {
+            name: name.to_vec(),
+            line: i,
+          }),
+          _ => {}
+        }
+        res
+      }
+      Some(_) => li.skip_fold(&nextline),
+    };
+  }
+
+  groups
+}
+
+/// The group references on a line. Empty if the line is not a GES line
+/// selecting groups.
+pub fn line_refs(line: &[u8], idx: usize) -> Vec<GroupRef> {
+  let selects_groups = line.len() > 8
+    && &line[0..8] == b"        "
+    && ["GRP ", "GRP>NOD ", "OGRP ", "DELGRP ", "DELGRP>NOD "]
+      .iter()
+      .any(|s| line[8..].starts_with(s.as_bytes()));
+
+  if !selects_groups {
+    return vec![];
+  }
+
+  let mut refs = vec![];
+  let mut start = 8;
+
+  while let Some(open) = line[start..].iter().position(|&b| b == b'\'') {
+    let open = start + open;
+    let close = match line[open + 1..].iter().position(|&b| b == b'\'') {
+      Some(c) => open + 1 + c,
+      None => break,
+    };
+
+    refs.push(GroupRef {
+      name: line[open + 1..close].trim_ascii().to_vec(),
+      line: idx,
+      range: open..close + 1,
+    });
+    start = close + 1;
+  }
+
+  refs
+}
+
+/// All group references in `lines`, in order of occurence
+pub fn group_refs<T: AsRef<[u8]>>(lines: &[T]) -> Vec<GroupRef> {
+  lines
+    .iter()
+    .enumerate()
+    .flat_map(|(i, l)| line_refs(l.as_ref(), i))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use group::{group_refs, groups, line_refs, Group};
+
+  const LINES: [&str; 14] = [
+    "$#                                                                         TITLE",
+    "GROUP / ",
+    "NAME hausbau",
+    "        PART 1 2",
+    "        END",
+    "GROUP / ",
+    "NAME dach",
+    "        GRP 'hausbau'",
+    "        DELGRP 'keller'  'garage'",
+    "        END",
+    "CNTAC /        2      36",
+    "NAME Self contact",
+    "        OGRP 'dach'",
+    "        END",
+  ];
+
+  #[test]
+  fn groups_can_be_read() {
+    let v = vec![
+      Group {
+        name: b"hausbau".to_vec(),
+        line: 1,
+      },
+      Group {
+        name: b"dach".to_vec(),
+        line: 5,
+      },
+    ];
+
+    assert_eq!(v, groups(&LINES));
+  }
+
+  #[test]
+  fn group_references_are_found() {
+    let refs = group_refs(&LINES);
+    let v: Vec<(&[u8], usize, usize)> = refs
+      .iter()
+      .map(|r| (r.name.as_ref(), r.line, r.range.start))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (&b"hausbau"[..], 7, 12),
+        (&b"keller"[..], 8, 15),
+        (&b"garage"[..], 8, 25),
+        (&b"dach"[..], 12, 13),
+      ],
+      v
+    );
+    assert!(line_refs(b"        PART 'hausbau'", 0).is_empty());
+    assert!(line_refs(b"NAME 'hausbau'", 0).is_empty());
+  }
+
+  #[test]
+  fn latin1_names_are_matched() {
+    let lines: [&[u8]; 5] = [
+      b"GROUP / ",
+      b"NAME Gel\xe4nde",
+      b"        END",
+      b"CNTAC /        2      36",
+      b"        GRP 'Gel\xe4nde' 'dach'",
+    ];
+    let refs = group_refs(&lines);
+
+    assert_eq!(b"Gel\xe4nde".to_vec(), groups(&lines)[0].name);
+    assert_eq!(2, refs.len());
+    assert_eq!(b"Gel\xe4nde".to_vec(), refs[0].name);
+    assert_eq!(12..21, refs[0].range);
+    assert_eq!(22..28, refs[1].range);
+  }
+}
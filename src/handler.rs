@@ -23,7 +23,7 @@ impl NeovimHandler {
       &mut args,
       "Not enough arguments in nvim_buf_updates_start!",
     )?)?;
-    let linedata = parse_vecstr(last_arg(
+    let linedata = parse_vecbytes(last_arg(
       &mut args,
       "Not enough arguments in nvim_buf_updates_start!",
     )?)?;
@@ -47,7 +47,7 @@ impl NeovimHandler {
   /// Parse a nvim_buf_update notification into a
   /// [Update](../event/enum.Event.html#variant.Update) event
   pub fn parse_update(&mut self, mut args: Vec<Value>) -> Result<Event, Error> {
-    let linedata = parse_vecstr(last_arg(
+    let linedata = parse_vecbytes(last_arg(
       &mut args,
       "Not enough arguments in nvim_buf_update!",
     )?)?;
@@ -94,6 +94,50 @@ impl NeovimHandler {
     Ok(Event::ChangedTick { buf, changedtick })
   }
 
+  /// Parse a GotoInclude notification into a
+  /// [GotoInclude](../event/enum.Event.html#variant.GotoInclude) event
+  pub fn parse_goto_include(
+    &mut self,
+    mut args: Vec<Value>,
+  ) -> Result<Event, Error> {
+    let line = parse_u64(&last_arg(
+      &mut args,
+      "Not enough arguments in GotoInclude!",
+    )?)?;
+    Ok(Event::GotoInclude { line })
+  }
+
+  /// Parse a GotoGroup notification into a
+  /// [GotoGroup](../event/enum.Event.html#variant.GotoGroup) event
+  pub fn parse_goto_group(
+    &mut self,
+    mut args: Vec<Value>,
+  ) -> Result<Event, Error> {
+    let col = parse_u64(&last_arg(
+      &mut args,
+      "Not enough arguments in GotoGroup!",
+    )?)?;
+    let line = parse_u64(&last_arg(
+      &mut args,
+      "Not enough arguments in GotoGroup!",
+    )?)?;
+    Ok(Event::GotoGroup { line, col })
+  }
+
+  /// Parse a RegenerateHeader notification into a
+  /// [RegenerateHeader](../event/enum.Event.html#variant.RegenerateHeader)
+  /// event
+  pub fn parse_regenerate_header(
+    &mut self,
+    mut args: Vec<Value>,
+  ) -> Result<Event, Error> {
+    let line = parse_u64(&last_arg(
+      &mut args,
+      "Not enough arguments in RegenerateHeader!",
+    )?)?;
+    Ok(Event::RegenerateHeader { line })
+  }
+
   /// Parse a nvim_buf_updates_end notification into a
   /// [UpdatesEnd](../event/enum.Event.html#variant.UpdatesEnd) event
   pub fn parse_updates_end(
@@ -149,6 +193,36 @@ impl Handler for NeovimHandler {
           error!("{}", reason);
         }
       }
+      "Validate" => {
+        info!("Validate");
+        if let Err(reason) = self.0.send(Event::Validate) {
+          error!("{}", reason);
+        }
+      }
+      "GotoInclude" => {
+        if let Ok(event) = self.parse_goto_include(args) {
+          info!("{:?}", event);
+          if let Err(reason) = self.0.send(event) {
+            error!("{}", reason);
+          }
+        }
+      }
+      "GotoGroup" => {
+        if let Ok(event) = self.parse_goto_group(args) {
+          info!("{:?}", event);
+          if let Err(reason) = self.0.send(event) {
+            error!("{}", reason);
+          }
+        }
+      }
+      "RegenerateHeader" => {
+        if let Ok(event) = self.parse_regenerate_header(args) {
+          info!("{:?}", event);
+          if let Err(reason) = self.0.send(event) {
+            error!("{}", reason);
+          }
+        }
+      }
       "quit" => {
         if let Err(reason) = self.0.send(Event::Quit) {
           error!("{}", reason);
@@ -194,19 +268,18 @@ pub fn parse_bool(value: &Value) -> Result<bool, Error> {
     .ok_or_else(|| failure::err_msg("cannot parse bool"))
 }
 
-/// Pare a `neovim_lib::Value` into a Vec<String>. Note that this method takes
-/// ownership of the value so it does not need to copy out the contained strings
-pub fn parse_vecstr(value: Value) -> Result<Vec<String>, Error> {
-  let mut res: Vec<String>;
+/// Pare a `neovim_lib::Value` into a Vec<Vec<u8>>, containing the bytes of
+/// the strings. Invalid UTF-8 is kept, so buffers in other encodings (e.g.
+/// Latin-1) can be handled. Note that this method takes ownership of the
+/// value so it does not need to copy out the contained strings
+pub fn parse_vecbytes(value: Value) -> Result<Vec<Vec<u8>>, Error> {
+  let mut res: Vec<Vec<u8>>;
   if let Value::Array(v) = value {
     res = Vec::with_capacity(v.len());
 
     for val in v {
       if let Value::String(s) = val {
-        match s.into_str() {
-          Some(string) => res.push(string),
-          None => return Err(failure::err_msg("non-utf8 values in array")),
-        }
+        res.push(s.into_bytes());
       } else {
         return Err(failure::err_msg("non-String value in array"));
       }
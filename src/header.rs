@@ -0,0 +1,210 @@
+//! This module provides the `$#` header comments of card lines. A header is
+//! the comment line directly above a line of a card, and names its cells.
+//! Every name ends at the last column of its cell, e.g.
+//!
+//! ```text
+//! $#         IDNOD               X               Y               Z
+//! NODE  /        1              0.             0.5              0.
+//! ```
+//!
+//! The names are the ones of the [`Field`](::card::field::Field)s of the
+//! cells.
+use std::ops::Range;
+
+use card::cell::Cell;
+use card::field::Field;
+use card::line::Line;
+use nocommentiter::CommentLess;
+use skipresult::SkipResult;
+
+/// Check if a line is a `$#` header comment
+pub fn is_header<T: AsRef<[u8]>>(line: &T) -> bool {
+  line.as_ref().starts_with(b"$#")
+}
+
+/// Create the header for a line of a card, given the fields of its cells.
+/// Names that are too long for their cell are cut. Returns `None` if the line
+/// has no cells, or none of them has a name.
+pub fn header(line: &Line, fields: &[Field]) -> Option<String> {
+  let cells = line.cells();
+  let width: usize = cells.iter().map(|c| c.width()).sum();
+
+  if fields.iter().all(|f| f.name.is_empty()) {
+    return None;
+  }
+
+  let mut header = vec![b' '; width.max(2)];
+  let mut start = 0;
+
+  for (i, (cell, field)) in cells.iter().zip(fields.iter()).enumerate() {
+    let end = start + cell.width();
+    // The first cell shares its columns with the `$#`
+    let room = if i == 0 {
+      cell.width().saturating_sub(2)
+    } else {
+      cell.width()
+    };
+    let name = &field.name.as_bytes()[..field.name.len().min(room)];
+
+    header[end - name.len()..end].copy_from_slice(name);
+    start = end;
+  }
+  header[..2].copy_from_slice(b"$#");
+
+  // Field names are ASCII, so this can not fail
+  let header = String::from_utf8(header).ok()?;
+  Some(header.trim_end().to_owned())
+}
+
+/// The entries of a header that are misplaced for the given line of a card,
+/// as ranges of columns (0-based, end exclusive). An entry is misplaced if it
+/// does not end at the last column of a cell. Entries inside a blank cell
+/// are accepted, since several `BLANK`s are often written for a wide one.
+pub fn misplaced(header: &str, line: &Line) -> Vec<Range<usize>> {
+  let mut cells = vec![];
+  let mut start = 0;
+
+  for cell in line.cells() {
+    let end = start + cell.width();
+    cells.push((cell, start..end));
+    start = end;
+  }
+
+  entries(header)
+    .filter(|e| {
+      !cells.iter().any(|(cell, r)| {
+        let blank = matches!(**cell, Cell::Blank(_));
+        r.end == e.end || blank && r.start <= e.start && e.end <= r.end
+      })
+    })
+    .collect()
+}
+
+/// The ranges of the entries of a header, i.e. of the words after the `$#`
+fn entries(header: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+  let bytes = header.as_bytes();
+  let mut pos = 2.min(bytes.len());
+
+  ::std::iter::from_fn(move || {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+      pos += 1;
+    }
+    let start = pos;
+    while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() {
+      pos += 1;
+    }
+
+    if start < pos {
+      Some(start..pos)
+    } else {
+      None
+    }
+  })
+}
+
+/// Create the header for the card line at index `idx` of `lines`, which can
+/// be a slice or [`Lines`](::lines::Lines). If `idx` is a header itself, the
+/// line below it is used. If the line has no cells (e.g. it belongs to a
+/// GES), the line containing the keyword is used. Returns the index of the
+/// line the header belongs to, and the header.
+pub fn header_at<'a, I, T>(lines: I, idx: usize) -> Option<(usize, String)>
+where
+  I: IntoIterator<Item = &'a T> + Copy,
+  T: AsRef<[u8]> + 'a,
+{
+  let target = match lines.into_iter().nth(idx) {
+    Some(l) if is_header(l) => idx + 1,
+    Some(_) => idx,
+    None => return None,
+  };
+
+  let mut li = lines.into_iter().enumerate().remove_comments();
+  let mut nextline = SkipResult::from(li.next());
+
+  while let Some((i, _)) = nextline.nextline {
+    if i > target {
+      break;
+    }
+
+    nextline = match nextline.nextline_kw {
+      None => SkipResult::from(li.next()),
+      Some(ref kw) => {
+        let card = li.card(kw);
+        let (res, parsed) = li.parse_card_as(card, &nextline);
+
+        if let Some(pl) = parsed.iter().find(|p| p.index == target) {
+          let pl = if pl.cells.is_empty() { &parsed[0] } else { pl };
+          return header(pl.line, card.fields(pl.line)).map(|h| (pl.index, h));
+        }
+        res
+      }
+    };
+  }
+
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use carddata::*;
+  use header::{header, header_at, misplaced};
+
+  #[test]
+  fn headers_can_be_created() {
+    assert_eq!(
+      Some(
+        "$#         IDNOD               X               Y               Z"
+          .to_owned()
+      ),
+      header(&NODE.lines[0], NODE.fields(&NODE.lines[0]))
+    );
+    assert_eq!(
+      Some(format!("$#{:>78}", "TITLE")),
+      header(&THNOD.lines[1], THNOD.fields(&THNOD.lines[1]))
+    );
+    assert_eq!(None, header(&THNOD.lines[2], THNOD.fields(&THNOD.lines[2])));
+  }
+
+  #[test]
+  fn misplaced_entries_are_found() {
+    let line = &RBODY.lines[0];
+
+    assert!(misplaced(
+      "$#          IDRB   BLANK    ITRBIDNODcog  IDNOD1  IDNOD2   ISENS",
+      line
+    )
+    .is_empty());
+    assert_eq!(
+      vec![21..25, 28..36, 38..44],
+      misplaced("$#          IDRB     ITRB   IDNODcog  IDNOD1", line)
+    );
+  }
+
+  const LINES: [&str; 9] = [
+    "$# wrong header",
+    "THNOD /     1000       0",
+    "NAME Impactor_COG",
+    "THNOD /        0       0",
+    "NAME Fixed end",
+    "        NOD        1       10",
+    "        END",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn header_at_cursor_can_be_created() {
+    let thnod = "$#         IDNOD    IFRA".to_owned();
+
+    assert_eq!(Some((1, thnod.clone())), header_at(&LINES, 0));
+    assert_eq!(Some((1, thnod.clone())), header_at(&LINES, 1));
+    assert_eq!(Some((3, thnod)), header_at(&LINES, 5));
+    assert_eq!(
+      Some(format!("$#{:>78}", "TITLE")),
+      header_at(&LINES, 4).map(|h| h.1)
+    );
+    assert_eq!(Some(8), header_at(&LINES, 8).map(|h| h.0));
+    assert_eq!(None, header_at(&LINES, 7));
+    assert_eq!(None, header_at(&LINES, 9));
+  }
+}
@@ -0,0 +1,327 @@
+//! This module provides [`Model`](::include::Model), the tree of files of a
+//! deck that are linked by `INCLU` cards. The first file is the root of the
+//! tree (usually the buffer), the included files are read from disk. Paths
+//! are resolved relative to the directory of the including file.
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str;
+
+use failure::{Error, ResultExt};
+
+use card::keyword::Keyword;
+use group::{self, Group};
+use settings::ModelSettings;
+use validation::{self, Diagnostic, Severity};
+
+/// The path given on an `INCLU` line. Returns `None` if the line is not an
+/// `INCLU` line, or gives no path (or one that is not valid UTF-8).
+pub fn included_file<T: AsRef<[u8]>>(line: &T) -> Option<&str> {
+  match Keyword::parse(line) {
+    Some(Keyword::Inclu) => {
+      let path = str::from_utf8(&line.as_ref()[8..]).ok()?.trim();
+      if path.is_empty() {
+        None
+      } else {
+        Some(path)
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Resolve the path of an included file relative to the directory of the
+/// including file. Absolute paths are returned unchanged.
+pub fn resolve<P: AsRef<Path>>(including: P, included: &str) -> PathBuf {
+  let included = Path::new(included);
+
+  if included.is_absolute() {
+    return included.to_path_buf();
+  }
+
+  match including.as_ref().parent() {
+    Some(dir) => dir.join(included),
+    None => included.to_path_buf(),
+  }
+}
+
+/// An `INCLU` card of a file. `line` is the index of its line (0-based),
+/// `path` the resolved path of the included file and `file` its index in the
+/// [`Model`](::include::Model), or `None` if it could not be read.
+#[derive(Debug, PartialEq)]
+pub struct Include {
+  pub line: usize,
+  pub path: PathBuf,
+  pub file: Option<usize>,
+}
+
+/// A file of a [`Model`](::include::Model). The lines of the first file
+/// may be borrowed, e.g. from the buffer, the included files own theirs.
+#[derive(Debug)]
+pub struct ModelFile<'a> {
+  pub path: PathBuf,
+  pub lines: Cow<'a, [Vec<u8>]>,
+  pub includes: Vec<Include>,
+}
+
+/// All files of a deck. Every file is only read once, even if it is included
+/// several times, so cyclic includes are no problem.
+#[derive(Debug)]
+pub struct Model<'a> {
+  pub files: Vec<ModelFile<'a>>,
+}
+
+impl<'a> Model<'a> {
+  /// Create the model of the file at `path` with the given lines, e.g. the
+  /// contents of a buffer. The lines are borrowed, the included files are
+  /// read from disk.
+  pub fn new<P: AsRef<Path>>(path: P, lines: &'a [Vec<u8>]) -> Model<'a> {
+    let mut model = Model { files: vec![] };
+    model.add_file(canonical(path.as_ref()), Cow::Borrowed(lines));
+    model
+  }
+
+  /// Read the model of the file at `path` from disk
+  pub fn load<P: AsRef<Path>>(path: P) -> Result<Model<'static>, Error> {
+    let lines = read_lines(path.as_ref())?;
+    let mut model = Model { files: vec![] };
+    model.add_file(canonical(path.as_ref()), Cow::Owned(lines));
+    Ok(model)
+  }
+
+  /// Add a file and all the files it includes, returns the index of the
+  /// file
+  fn add_file(&mut self, path: PathBuf, lines: Cow<'a, [Vec<u8>]>) -> usize {
+    let idx = self.files.len();
+    let included: Vec<(usize, PathBuf)> = lines
+      .iter()
+      .enumerate()
+      .filter_map(|(i, l)| included_file(l).map(|f| (i, resolve(&path, f))))
+      .collect();
+
+    self.files.push(ModelFile {
+      path,
+      lines,
+      includes: vec![],
+    });
+
+    for (line, incpath) in included {
+      let incpath = canonical(&incpath);
+      let file = match self.find(&incpath) {
+        Some(i) => Some(i),
+        None => match read_lines(&incpath) {
+          Ok(l) => Some(self.add_file(incpath.clone(), Cow::Owned(l))),
+          Err(e) => {
+            warn!("{}", e);
+            None
+          }
+        },
+      };
+
+      self.files[idx].includes.push(Include {
+        line,
+        path: incpath,
+        file,
+      });
+    }
+
+    idx
+  }
+
+  /// The index of the file with the given path
+  pub fn find<P: AsRef<Path>>(&self, path: P) -> Option<usize> {
+    let path = canonical(path.as_ref());
+    self.files.iter().position(|f| f.path == path)
+  }
+
+  /// The `INCLU` card on the given line of a file
+  pub fn include_at(&self, file: usize, line: usize) -> Option<&Include> {
+    self
+      .files
+      .get(file)?
+      .includes
+      .iter()
+      .find(|i| i.line == line)
+  }
+
+  /// All [`Group`](::group::Group)s defined in the files of the model,
+  /// together with the index of their file
+  pub fn groups(&self) -> Vec<(usize, Group)> {
+    self
+      .files
+      .iter()
+      .enumerate()
+      .flat_map(|(i, f)| {
+        group::groups(&f.lines).into_iter().map(move |g| (i, g))
+      }).collect()
+  }
+
+  /// The definition of the group with the given name. If it is defined more
+  /// than once, the first definition is returned.
+  pub fn find_group(&self, name: &[u8]) -> Option<(usize, Group)> {
+    self.groups().into_iter().find(|(_, g)| g.name == name)
+  }
+
+  /// [`Validate`](::validation::validate) all files of the model. The
+  /// diagnostics are returned with the index of their file. Included files
+  /// that could not be read are reported on their `INCLU` line, references
+  /// to groups that are not defined in any file on the line of the GES.
+  /// Included files are validated with the settings of the first file.
+  pub fn validate(&self) -> Vec<(usize, Diagnostic)> {
+    let mut diags = vec![];
+    let groups = self.groups();
+    let settings = self
+      .files
+      .first()
+      .map(|f| ModelSettings::from_lines(&f.lines))
+      .unwrap_or_default();
+
+    for (i, file) in self.files.iter().enumerate() {
+      for inc in file.includes.iter().filter(|inc| inc.file.is_none()) {
+        diags.push((
+          i,
+          Diagnostic {
+            line: inc.line,
+            range: 8..file.lines[inc.line].len(),
+            severity: Severity::Error,
+            message: format!(
+              "Included file '{}' could not be read",
+              inc.path.display()
+            ),
+          },
+        ));
+      }
+
+      diags.extend(
+        validation::validate_with_settings(&file.lines, &settings)
+          .into_iter()
+          .map(|d| (i, d)),
+      );
+
+      for r in group::group_refs(&file.lines) {
+        if !groups.iter().any(|(_, g)| g.name == r.name) {
+          diags.push((
+            i,
+            Diagnostic {
+              line: r.line,
+              range: r.range,
+              severity: Severity::Error,
+              message: format!(
+                "Group '{}' is not defined",
+                String::from_utf8_lossy(&r.name)
+              ),
+            },
+          ));
+        }
+      }
+    }
+
+    diags
+  }
+}
+
+/// The canonical form of a path, or the path itself if it does not exist
+fn canonical(path: &Path) -> PathBuf {
+  fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Read the lines of a file. The lines are kept as bytes, like the lines of
+/// a buffer, so the columns of diagnostics match for files that are not
+/// valid UTF-8.
+fn read_lines(path: &Path) -> Result<Vec<Vec<u8>>, Error> {
+  let bytes = fs::read(path)
+    .with_context(|_| format!("Could not read '{}'", path.display()))?;
+
+  let bytes = bytes.strip_suffix(b"\n").unwrap_or(&bytes);
+  if bytes.is_empty() {
+    return Ok(vec![]);
+  }
+
+  Ok(
+    bytes
+      .split(|&b| b == b'\n')
+      .map(|l| l.strip_suffix(b"\r").unwrap_or(l).to_vec())
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::{Path, PathBuf};
+
+  use include::{included_file, resolve, Model};
+  use validation;
+
+  #[test]
+  fn include_lines_are_recognized() {
+    assert_eq!(Some("sub/a.inc"), included_file(&"INCLU / sub/a.inc   "));
+    assert_eq!(None, included_file(&"INCLU /    "));
+    assert_eq!(None, included_file(&"NODE  / sub/a.inc"));
+    assert_eq!(
+      PathBuf::from("/deck/sub/a.inc"),
+      resolve("/deck/main.pc", "sub/a.inc")
+    );
+    assert_eq!(
+      PathBuf::from("/abs/a.inc"),
+      resolve("/deck/main.pc", "/abs/a.inc")
+    );
+  }
+
+  const LINES: [&str; 8] = [
+    "INCLU / sub/nodes.inc",
+    "INCLU / missing.inc",
+    "$",
+    "NODE  /        1              0.             0.5              0.",
+    "GROUP / ",
+    "NAME all",
+    "        GRP 'shells' 'nowhere'",
+    "        END",
+  ];
+
+  #[test]
+  fn include_tree_can_be_read() {
+    let lines: Vec<Vec<u8>> =
+      LINES.iter().map(|l| l.as_bytes().to_vec()).collect();
+    let model = Model::new("files/include/main.pc", &lines);
+
+    assert_eq!(3, model.files.len());
+    assert!(model.files[1].path.ends_with("files/include/sub/nodes.inc"));
+    assert!(model.files[2].path.ends_with("files/include/shells.inc"));
+    assert_eq!(Some(1), model.include_at(0, 0).and_then(|i| i.file));
+    assert_eq!(None, model.include_at(0, 1).and_then(|i| i.file));
+    // shells.inc includes nodes.inc again, which is not read twice
+    assert_eq!(Some(1), model.include_at(2, 1).and_then(|i| i.file));
+    assert_eq!(
+      Some(2),
+      model.find(Path::new("files/include/sub/../shells.inc"))
+    );
+
+    assert_eq!(
+      Some((2, 4)),
+      model.find_group(b"shells").map(|(f, g)| (f, g.line))
+    );
+
+    let diags = model.validate();
+    assert_eq!(3, diags.len());
+    assert_eq!((0, 1), (diags[0].0, diags[0].1.line));
+    assert!(diags[0].1.message.contains("missing.inc"));
+    assert_eq!((0, 6), (diags[1].0, diags[1].1.line));
+    assert_eq!("Group 'nowhere' is not defined", diags[1].1.message);
+    assert_eq!((2, 3), (diags[2].0, diags[2].1.line));
+  }
+
+  #[test]
+  fn diagnostics_use_byte_columns() {
+    let line =
+      b"NODE  / \xe4      1              0.             0.5 x".to_vec();
+    let lines = vec![line.clone()];
+    let model = Model::new("files/include/latin1.pc", &lines);
+    let diags = model.validate();
+
+    let expected = validation::validate(&[line]);
+
+    assert_eq!(expected.len(), diags.len());
+    assert!(expected.iter().zip(diags.iter()).all(|(e, (_, d))| e == d));
+    assert_eq!(8..16, diags[0].1.range);
+  }
+}
@@ -3,14 +3,27 @@ extern crate failure;
 #[macro_use]
 extern crate log;
 extern crate neovim_lib;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate toml;
 
 pub mod card;
 pub mod carddata;
 pub mod codeyard;
+pub mod cst;
+pub mod curve;
 pub mod event;
 pub mod folds;
+pub mod frame;
+pub mod group;
 pub mod handler;
+pub mod header;
+pub mod include;
 pub mod lines;
 pub mod neovim_ext;
 pub mod nocommentiter;
+pub mod schema;
+pub mod settings;
 pub mod skipresult;
+pub mod validation;
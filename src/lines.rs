@@ -1,52 +1,142 @@
-//! This module holds the datastructure for the Lines of the buffer. For now,
-//! it's simply a `Vec<String>` with an appropriate API.
+//! This module holds the datastructure for the Lines of the buffer. It's a
+//! gap buffer adapted to lines instead of chars: The lines before the gap are
+//! stored in one `Vec`, the ones after it in reversed order in another one.
+//! An update moves the gap to the changed lines and replaces them there, so
+//! its cost only depends on the distance to the last update and the number of
+//! lines changed, not on the size of the buffer. Indexing stays O(1).
 //!
-//! Future ideas, if performance isn't enough: Skip list, gap buffer (adapted to
-//! lines instead of strings), rope (adapted to lines instead of strings)
+//! The lines are stored as bytes, just as neovim sends them, since decks are
+//! not always valid UTF-8 (e.g. Latin-1 encoded titles). The parsers work on
+//! bytes, and so do [`iter`](::lines::Lines::iter) and
+//! [`to_vec`](::lines::Lines::to_vec). Strings are only decoded where they
+//! are shown, e.g. in messages.
+//!
+//! Since the lines are not contiguous, there's no `Deref` to a slice of
+//! them. Use [`iter`](::lines::Lines::iter) or indexing, or
+//! [`make_contiguous`](::lines::Lines::make_contiguous) if a slice is needed.
+use std::iter::{Chain, Rev};
 use std::ops;
+use std::slice;
+
+use failure::{err_msg, Error};
 
 /// The struct to hold the lines.
-#[derive(Debug)]
-pub struct Lines(pub Vec<String>);
+#[derive(Debug, Default)]
+pub struct Lines {
+  front: Vec<Vec<u8>>, // the lines before the gap
+  back: Vec<Vec<u8>>,  // the lines after the gap, in reversed order
+}
+
+/// The iterator over the lines of [`Lines`](::lines::Lines)
+pub type Iter<'a> =
+  Chain<slice::Iter<'a, Vec<u8>>, Rev<slice::Iter<'a, Vec<u8>>>>;
 
 impl Lines {
-  // Create a new Lines struct from a `Vec<String>`.
-  pub fn new(v: Vec<String>) -> Lines {
-    Lines { 0: v }
+  // Create a new Lines struct from a `Vec<Vec<u8>>`.
+  pub fn new(v: Vec<Vec<u8>>) -> Lines {
+    Lines {
+      front: v,
+      back: Vec::new(),
+    }
   }
 
   // Returns the number of lines
   pub fn len(&self) -> usize {
-    self.0.len()
+    self.front.len() + self.back.len()
   }
 
   // Check if there are any lines
   pub fn is_empty(&self) -> bool {
-    self.0.is_empty()
+    self.front.is_empty() && self.back.is_empty()
+  }
+
+  /// The line at index `idx`, or `None` if it's out of bounds
+  pub fn get(&self, idx: usize) -> Option<&Vec<u8>> {
+    if idx < self.front.len() {
+      self.front.get(idx)
+    } else {
+      let i = idx - self.front.len();
+      self.back.len().checked_sub(i + 1).map(|j| &self.back[j])
+    }
   }
 
-  /// Update Lines:
-  ///   * `first` and `last` are zero-indexed (just as Lines itself)
-  ///   * If `numreplaced` is zero, the lines were added before line `first`
-  pub fn update(&mut self, first: u64, last: u64, linedata: Vec<String>) {
-    let range = first as usize..last as usize;
-    let _v = self.0.splice(range, linedata);
+  /// Iterate over the lines
+  pub fn iter<'a>(&'a self) -> Iter<'a> {
+    self.front.iter().chain(self.back.iter().rev())
+  }
+
+  /// Copy the lines into a `Vec`
+  pub fn to_vec(&self) -> Vec<Vec<u8>> {
+    self.iter().cloned().collect()
+  }
+
+  /// Move the gap to the end, and return all lines as a slice. This takes
+  /// time proportional to the number of lines after the gap, so it should
+  /// not be called after every update.
+  pub fn make_contiguous(&mut self) -> &[Vec<u8>] {
+    self.move_gap(self.len());
+    &self.front
+  }
+
+  /// Replace the lines `first..last` (zero-indexed, end exclusive) by
+  /// `linedata`. If `first == last`, the lines are inserted before line
+  /// `first`. Returns an error if `first..last` is not a range of lines.
+  pub fn update(
+    &mut self,
+    first: u64,
+    last: u64,
+    linedata: Vec<Vec<u8>>,
+  ) -> Result<(), Error> {
+    let (first, last) = (first as usize, last as usize);
+    if first > last || last > self.len() {
+      return Err(err_msg(format!(
+        "Invalid update of lines {}..{} of {}",
+        first,
+        last,
+        self.len()
+      )));
+    }
+
+    self.move_gap(last);
+    self.front.truncate(first);
+    self.front.extend(linedata);
+
+    Ok(())
+  }
+
+  /// Move the gap before line `idx`
+  fn move_gap(&mut self, idx: usize) {
+    while self.front.len() > idx {
+      if let Some(l) = self.front.pop() {
+        self.back.push(l);
+      }
+    }
+    while self.front.len() < idx {
+      match self.back.pop() {
+        Some(l) => self.front.push(l),
+        None => break,
+      }
+    }
   }
 }
 
 impl ops::Index<usize> for Lines {
-  type Output = String;
+  type Output = Vec<u8>;
 
-  fn index(&self, idx: usize) -> &String {
-    &self.0[idx]
+  fn index(&self, idx: usize) -> &Vec<u8> {
+    match self.get(idx) {
+      Some(l) => l,
+      None => panic!("Line {} out of bounds of {} lines", idx, self.len()),
+    }
   }
 }
 
-impl ops::Deref for Lines {
-  type Target = [String];
+impl<'a> IntoIterator for &'a Lines {
+  type Item = &'a Vec<u8>;
+  type IntoIter = Iter<'a>;
 
-  fn deref(&self) -> &[String] {
-    &self.0
+  fn into_iter(self) -> Iter<'a> {
+    self.iter()
   }
 }
 
@@ -54,52 +144,90 @@ impl ops::Deref for Lines {
 mod tests {
   use lines::Lines;
 
-  const LINES: [&'static str; 8] =
+  const LINES: [&str; 8] =
     ["This", "is", "an", "example", "of", "some", "lines", "."];
 
   #[test]
   fn lines_can_delete() {
-    let v = LINES.iter().map(|s| s.to_string()).collect();
+    let v = LINES.iter().map(|s| s.as_bytes().to_vec()).collect();
     let mut l = Lines::new(v);
 
-    l.update(1, 7, Vec::new());
-    assert_eq!(l[0], "This");
-    assert_eq!(l[1], ".");
+    l.update(1, 7, Vec::new()).unwrap();
+    assert_eq!(l[0], b"This");
+    assert_eq!(l[1], b".");
     assert_eq!(l.len(), 2);
   }
 
   #[test]
   fn lines_can_insert() {
-    let v = LINES.iter().map(|s| s.to_string()).collect();
+    let v = LINES.iter().map(|s| s.as_bytes().to_vec()).collect();
     let mut l = Lines::new(v);
 
     let newlines = vec![
-      "haaargl".to_string(),
-      "waaarglll".to_string(),
-      "blaaargl".to_string(),
+      b"haaargl".to_vec(),
+      b"waaarglll".to_vec(),
+      b"blaaargl".to_vec(),
     ];
 
-    l.update(2, 2, newlines);
-    assert_eq!(l[2], "haaargl");
+    l.update(2, 2, newlines).unwrap();
+    assert_eq!(l[2], b"haaargl");
     assert_eq!(l.len(), 11);
   }
 
   #[test]
   fn lines_can_update() {
-    let v = LINES.iter().map(|s| s.to_string()).collect();
+    let v = LINES.iter().map(|s| s.as_bytes().to_vec()).collect();
     let mut l = Lines::new(v);
 
     let newlines = vec![
-      "haaargl".to_string(),
-      "waaarglll".to_string(),
-      "blaaargl".to_string(),
+      b"haaargl".to_vec(),
+      b"waaarglll".to_vec(),
+      b"blaaargl".to_vec(),
     ];
 
-    l.update(1, 7, newlines);
-    assert_eq!(l[0], "This");
-    assert_eq!(l[3], "blaaargl");
-    assert_eq!(l[4], ".");
+    l.update(1, 7, newlines).unwrap();
+    assert_eq!(l[0], b"This");
+    assert_eq!(l[3], b"blaaargl");
+    assert_eq!(l[4], b".");
     assert_eq!(l.len(), 5);
   }
 
+  #[test]
+  fn lines_match_vec_after_updates() {
+    let mut v: Vec<Vec<u8>> =
+      LINES.iter().map(|s| s.as_bytes().to_vec()).collect();
+    let mut l = Lines::new(v.clone());
+
+    let updates = [(6, 8, 1), (0, 0, 2), (3, 5, 0), (1, 2, 3), (7, 7, 1)];
+
+    for (n, &(first, last, added)) in updates.iter().enumerate() {
+      let newlines: Vec<Vec<u8>> = (0..added)
+        .map(|i| format!("new{}{}", n, i).into_bytes())
+        .collect();
+
+      let _ = v.splice(first..last, newlines.clone());
+      l.update(first as u64, last as u64, newlines).unwrap();
+
+      assert_eq!(v, l.to_vec());
+      assert_eq!(v.len(), l.len());
+      for (i, line) in v.iter().enumerate() {
+        assert_eq!(line, &l[i]);
+      }
+      assert_eq!(None, l.get(v.len()));
+    }
+
+    assert_eq!(v.as_slice(), l.make_contiguous());
+    assert_eq!(v, l.to_vec());
+  }
+
+  #[test]
+  fn invalid_updates_are_errors() {
+    let v = LINES.iter().map(|s| s.as_bytes().to_vec()).collect();
+    let mut l = Lines::new(v);
+
+    assert!(l.update(3, 2, vec![]).is_err());
+    assert!(l.update(7, 9, vec![]).is_err());
+    assert!(l.update(8, 8, vec![b"end".to_vec()]).is_ok());
+    assert_eq!(9, l.len());
+  }
 }
@@ -28,9 +28,7 @@ impl BufferExt for Buffer {
     neovim: &mut Neovim,
     send_buffer: bool,
   ) -> Result<(), CallError> {
-    let mut v = Vec::new();
-    v.push(self.get_value().clone());
-    v.push(Value::from(send_buffer));
+    let v = vec![self.get_value().clone(), Value::from(send_buffer)];
     neovim
       .session
       .call("nvim_buf_event_sub", v)
@@ -40,8 +38,7 @@ impl BufferExt for Buffer {
 
   /// since: xxxx
   fn event_unsub(&self, neovim: &mut Neovim) -> Result<(), CallError> {
-    let mut v = Vec::new();
-    v.push(self.get_value().clone());
+    let v = vec![self.get_value().clone()];
     neovim
       .session
       .call("nvim_buf_event_unsub", v)
@@ -2,18 +2,25 @@
 //! datastructure for the folding functionality of nvimpam.
 //!
 //! It returns enumerated Lines, but skips Comments (lines starting with `$` or
-//! `#`). All skip functions, used by
-//! [`add_folds`](::folds::FoldList::add_folds), work on a
-//! [`NoCommentIter`](NoCommentIter).
+//! `#`). All skip functions work on a [`NoCommentIter`](NoCommentIter), and
+//! the [`CardNodes`](::cst::CardNodes) of the concrete syntax tree, which
+//! [`add_folds`](::folds::FoldList::add_folds) uses, walk the cards with
+//! it.
 use std::default::Default;
 
+use card::cell::Value;
 use card::ges::GesType;
 use card::keyword::Keyword;
 use card::line::CondResult;
-use card::line::Line;
+use card::line::{Case, Line, ParsedLine};
 use card::Card;
 use skipresult::SkipResult;
 
+/// Check if a line is a comment, i.e. starts with `$` or `#`
+pub fn is_comment(line: &[u8]) -> bool {
+  line.first() == Some(&b'$') || line.first() == Some(&b'#')
+}
+
 /// Designates that the comments have been removed.
 pub trait CommentLess {
   fn remove_comments(self) -> NoCommentIter<Self>
@@ -23,22 +30,25 @@ pub trait CommentLess {
 
 /// The struct simply holds a type instance. Skipping comments is done in the
 /// Iterator implementation.
+///
+/// It also holds the `INPUTVERSION` of the deck, which is used to look up the
+/// layouts of the cards, see [`Card::for_version`](::card::Card::for_version).
+/// It is updated whenever an `INPUTVERSION` card is walked.
 pub struct NoCommentIter<I> {
   it: I,
+  version: Option<u32>,
 }
 
 impl<'a, I, T: 'a> Iterator for NoCommentIter<I>
 where
-  T: AsRef<str>,
+  T: AsRef<[u8]>,
   I: Iterator<Item = (usize, &'a T)>,
 {
   type Item = (usize, &'a T);
 
   fn next(&mut self) -> Option<Self::Item> {
-    while let Some((i, n)) = self.it.next() {
-      let t = n.as_ref();
-      let l = t.len();
-      if !(l > 0 && (t.as_bytes()[0] == b'#' || t.as_bytes()[0] == b'$')) {
+    for (i, n) in self.it.by_ref() {
+      if !is_comment(n.as_ref()) {
         return Some((i, n));
       }
     }
@@ -48,14 +58,61 @@ where
 
 impl<I> CommentLess for I {
   fn remove_comments(self) -> NoCommentIter<I> {
-    NoCommentIter { it: self }
+    NoCommentIter {
+      it: self,
+      version: None,
+    }
+  }
+}
+
+/// The state needed while walking through the lines of a card, see
+/// [`walk_card`](NoCommentIter::walk_card).
+struct CardState<'a, T: 'a> {
+  line: &'a T,                 // line we're currently processing
+  lineidx: usize,              // index of the currently processed line
+  linekw: Option<Keyword>,     // Keyword of the currently processed line
+  previdx: Option<usize>,      // index of the last line of the card
+  conds: Vec<CondResult>,      // the results of the conditionals
+  case: Option<&'static Case>, // the case of the Switch or Select we're in
+}
+
+impl<'a, T: 'a> CardState<'a, T>
+where
+  T: AsRef<[u8]>,
+{
+  /// The result of a walk ending on the current line
+  fn result(&self) -> SkipResult<'a, T> {
+    SkipResult {
+      nextline: Some((self.lineidx, self.line)),
+      nextline_kw: self.linekw,
+      skip_end: self.previdx,
+    }
+  }
+}
+
+impl<I> NoCommentIter<I> {
+  /// Set the `INPUTVERSION` to use until an `INPUTVERSION` card is found,
+  /// e.g. the version of the deck including the lines
+  pub fn with_version(mut self, version: Option<u32>) -> NoCommentIter<I> {
+    self.version = version;
+    self
+  }
+
+  /// The `INPUTVERSION` of the deck, as far as it is known
+  pub fn version(&self) -> Option<u32> {
+    self.version
+  }
+
+  /// The layout of the card for `kw` in the current `INPUTVERSION`
+  pub fn card(&self, kw: &Keyword) -> &'static Card {
+    Card::for_version(kw, self.version)
   }
 }
 
 impl<'a, I, T: 'a> NoCommentIter<I>
 where
   I: Iterator<Item = (usize, &'a T)>,
-  T: AsRef<str>,
+  T: AsRef<[u8]>,
 {
   /// Advance the iterator until meeting the first line with a keyword. Return
   /// the index and a reference to that line. If no line starts with a
@@ -76,10 +133,7 @@ where
         Some(n) => {
           let nextline_kw = Keyword::parse(n.1);
           if nextline_kw.is_some() {
-            let skip_end = match prevline {
-              Some((i, _l)) => Some(i),
-              None => None,
-            };
+            let skip_end = prevline.map(|(i, _l)| i);
             return SkipResult {
               nextline,
               nextline_kw,
@@ -110,6 +164,19 @@ where
   ///      in the second option.
   ///
   pub fn skip_ges<'b>(&'b mut self, ges: &GesType) -> SkipResult<'a, T> {
+    self.walk_ges(ges, &mut |_, _| {})
+  }
+
+  /// The implementation of [`skip_ges`](NoCommentIter::skip_ges). Calls `f`
+  /// with the index and the line for every line that belongs to the GES.
+  fn walk_ges<'b, F>(
+    &'b mut self,
+    ges: &GesType,
+    f: &mut F,
+  ) -> SkipResult<'a, T>
+  where
+    F: FnMut(usize, &'a T),
+  {
     let mut previdx = None;
     let mut idx;
     let mut line;
@@ -126,6 +193,7 @@ where
     }
 
     while ges_contains_line {
+      f(idx, line);
       let tmp = self.next();
       match tmp {
         None => {
@@ -144,6 +212,7 @@ where
     }
 
     if ges.ended_by(&line) {
+      f(idx, line);
       previdx = Some(idx);
       let nextline = self.next();
 
@@ -176,7 +245,7 @@ where
   ) -> SkipResult<'a, T> {
     let card: &Card = match nextline.nextline_kw {
       None => return Default::default(),
-      Some(ref k) => k.into(),
+      Some(ref k) => self.card(k),
     };
 
     if card.ownfold {
@@ -199,186 +268,319 @@ where
   ) -> SkipResult<'a, T> {
     let card: &Card = match nextline.nextline_kw {
       None => unreachable!(),
-      Some(ref k) => k.into(),
+      Some(ref k) => self.card(k),
     };
-    let mut cardlines = card.lines.iter();
+
+    self.walk_card(card, nextline, &mut |_, _, _, _| {})
+  }
+
+  /// Like [`skip_card`](NoCommentIter::skip_card), but also parse all lines
+  /// of the card into their [`ParsedCell`](::card::line::ParsedCell)s.
+  pub fn parse_card<'b>(
+    &'b mut self,
+    nextline: &SkipResult<'a, T>,
+  ) -> (SkipResult<'a, T>, Vec<ParsedLine<'a>>) {
+    let card: &Card = match nextline.nextline_kw {
+      None => unreachable!(),
+      Some(ref k) => self.card(k),
+    };
+
+    self.parse_card_as(card, nextline)
+  }
+
+  /// Like [`parse_card`](NoCommentIter::parse_card), but parse the lines
+  /// according to the given `card` instead of looking it up by the keyword.
+  pub fn parse_card_as<'b>(
+    &'b mut self,
+    card: &Card,
+    nextline: &SkipResult<'a, T>,
+  ) -> (SkipResult<'a, T>, Vec<ParsedLine<'a>>) {
+    let mut parsed = vec![];
+
+    let res = self.walk_card(card, nextline, &mut |i, l, cardline, _| {
+      parsed.push(ParsedLine {
+        index: i,
+        line: cardline,
+        cells: cardline.parse(l),
+      })
+    });
+
+    (res, parsed)
+  }
+
+  /// Walk through the lines of `card`, starting with the keyword line given
+  /// in `nextline`. Calls `f` with the index, the line, the
+  /// [`Line`](::card::line::Line) of the card and the
+  /// [`Case`](::card::line::Case) of the innermost
+  /// [`Switch`](::card::line::Line::Switch) or
+  /// [`Select`](::card::line::Line::Select) it was chosen by, for every line
+  /// that belongs to the card. Used to build the
+  /// [`CardNode`](::cst::CardNode)s of a deck.
+  pub fn walk_card<'b, F>(
+    &'b mut self,
+    card: &Card,
+    nextline: &SkipResult<'a, T>,
+    f: &mut F,
+  ) -> SkipResult<'a, T>
+  where
+    F: FnMut(usize, &'a T, &'static Line, Option<&'static Case>),
+  {
+    let cardlines: &'static [Line] = card.lines;
     let mut conds: Vec<CondResult> = vec![]; // the vec to hold the conditionals
 
-    let cardline = match cardlines.next() {
+    let (cardline, cardlines) = match cardlines.split_first() {
       None => unreachable!(),
       Some(c) => c,
     };
 
-    if let Line::Provides(_s, ref c) = cardline {
-      match nextline.nextline {
-        None => unreachable!(),
-        Some((_, l)) => conds.push(c.evaluate(l)),
+    let kwidx = match nextline.nextline {
+      None => unreachable!(),
+      Some((i, l)) => {
+        if let Line::Provides(_s, ref c) = *cardline {
+          conds.push(c.evaluate(l));
+        }
+        if card.keyword == Keyword::Inputversion {
+          self.version = match cardline.parse(l).get(1).map(|c| &c.value) {
+            Some(Value::Integer(v)) if *v >= 0 => Some(*v as u32),
+            _ => self.version,
+          };
+        }
+        f(i, l, cardline, None);
+        i
       }
-    }
-
-    let mut line; // line of the iterator we're currently processing
-    let mut lineidx; // index of the currently processed line
-    let mut linekw; // Keyword of the currently processed line
-    let mut previdx = None; // index of the last line of the card
-    let mut tmp;
+    };
 
-    match self.next() {
+    let mut state = match self.next() {
       None => return Default::default(),
-      Some((i, l)) => {
-        line = l;
-        lineidx = i;
-        linekw = Keyword::parse(line);
-      }
+      Some((i, l)) => CardState {
+        line: l,
+        lineidx: i,
+        linekw: Keyword::parse(l),
+        previdx: Some(kwidx),
+        conds,
+        case: None,
+      },
+    };
+
+    match self.walk_lines(cardlines, &mut state, f) {
+      Some(res) => res,
+      None => state.result(),
     }
+  }
 
-    // TODO: Maybe this is clearer with loop?
+  /// Walk through the given lines of a card, see
+  /// [`walk_card`](NoCommentIter::walk_card). Returns `Some` if the card
+  /// ended before all lines were walked, i.e. the file ended or a line
+  /// started with a keyword.
+  fn walk_lines<'b, F>(
+    &'b mut self,
+    cardlines: &'static [Line],
+    state: &mut CardState<'a, T>,
+    f: &mut F,
+  ) -> Option<SkipResult<'a, T>>
+  where
+    F: FnMut(usize, &'a T, &'static Line, Option<&'static Case>),
+  {
     for cardline in cardlines {
       match *cardline {
         Line::Provides(_s, ref c) => {
-          conds.push(c.evaluate(&line));
-          let tmp = self.next();
-          match tmp {
-            None => {
-              return SkipResult {
-                skip_end: Some(lineidx),
-                ..Default::default()
-              };
-            }
-            Some((i, l)) => {
-              previdx = Some(lineidx);
-              line = l;
-              lineidx = i;
-              linekw = Keyword::parse(l);
-            }
+          state.conds.push(c.evaluate(&state.line));
+          if let res @ Some(_) = self.advance(state, cardline, f) {
+            return res;
           }
         }
         Line::Ges(ref g) => {
-          let contains = g.contains(line);
-          let ended = g.ended_by(line);
-          if contains || ended {
-            if ended {
-              let tmp = self.next();
-              match tmp {
-                None => {
-                  return SkipResult {
-                    skip_end: Some(lineidx),
-                    ..Default::default()
-                  };
-                }
-                Some((i, l)) => {
-                  return SkipResult {
-                    nextline: Some((i, l)),
-                    nextline_kw: Keyword::parse(l),
-                    skip_end: Some(lineidx),
-                  }
-                }
-              }
-            } else {
-              tmp = self.skip_ges(g);
-
-              match tmp.nextline {
-                None => match tmp.skip_end {
-                  None => return Default::default(),
-                  Some(i) => {
-                    return SkipResult {
-                      skip_end: Some(i),
-                      ..Default::default()
-                    }
-                  }
-                },
-                Some((i, l)) => {
-                  line = l;
-                  lineidx = i;
-                  linekw = tmp.nextline_kw;
-                  previdx = tmp.skip_end.or(previdx);;
-                }
-              }
-            }
-          }
-        }
-        Line::Cells(_s) => {
-          if linekw.is_some() {
-            return SkipResult {
-              nextline: Some((lineidx, line)),
-              nextline_kw: linekw,
-              skip_end: previdx,
-            };
-          } else {
+          let contains = g.contains(state.line);
+          let ended = g.ended_by(state.line);
+          if ended {
+            f(state.lineidx, state.line, cardline, state.case);
             let tmp = self.next();
             match tmp {
               None => {
-                return SkipResult {
-                  skip_end: Some(lineidx),
+                return Some(SkipResult {
+                  skip_end: Some(state.lineidx),
                   ..Default::default()
-                };
+                });
+              }
+              Some((i, l)) => {
+                return Some(SkipResult {
+                  nextline: Some((i, l)),
+                  nextline_kw: Keyword::parse(l),
+                  skip_end: Some(state.lineidx),
+                })
               }
+            }
+          } else if contains {
+            let case = state.case;
+            f(state.lineidx, state.line, cardline, case);
+            let tmp = self.walk_ges(g, &mut |i, l| f(i, l, cardline, case));
+
+            match tmp.nextline {
+              None => return Some(tmp),
               Some((i, l)) => {
-                previdx = Some(lineidx);
-                line = l;
-                lineidx = i;
-                linekw = Keyword::parse(l);
+                state.line = l;
+                state.lineidx = i;
+                state.linekw = tmp.nextline_kw;
+                state.previdx = tmp.skip_end.or(state.previdx);
               }
             }
           }
         }
+        Line::Cells(_s) => {
+          if state.linekw.is_some() {
+            return Some(state.result());
+          } else if let res @ Some(_) = self.advance(state, cardline, f) {
+            return res;
+          }
+        }
         Line::Optional(_s, i) => {
-          if conds.get(i as usize) != Some(&CondResult::Bool(true)) {
+          if state.conds.get(i as usize) != Some(&CondResult::Bool(true)) {
             continue;
-          } else if let Some(kw) = Keyword::parse(line) {
-            return SkipResult {
-              nextline: Some((lineidx, line)),
-              nextline_kw: Some(kw),
-              skip_end: previdx,
-            };
-          } else {
-            let tmp = self.next();
-            match tmp {
-              None => {
-                return SkipResult {
-                  skip_end: Some(lineidx),
-                  ..Default::default()
-                };
-              }
-              Some((i, l)) => {
-                previdx = Some(lineidx);
-                line = l;
-                lineidx = i;
-                linekw = Keyword::parse(l);
-              }
-            }
+          } else if state.linekw.is_some() {
+            return Some(state.result());
+          } else if let res @ Some(_) = self.advance(state, cardline, f) {
+            return res;
           }
         }
         Line::Repeat(_s, i) => {
-          let num = match conds.get(i as usize) {
-            Some(CondResult::Number(Some(u))) => u,
+          let num = match state.conds.get(i as usize) {
+            Some(CondResult::Number(Some(u))) => *u,
             _ => continue,
           };
 
-          for _ in 0..*num {
-            let tmp = self.next();
+          for _ in 0..num {
+            if state.linekw.is_some() {
+              return Some(state.result());
+            } else if let res @ Some(_) = self.advance(state, cardline, f) {
+              return res;
+            }
+          }
+        }
+        Line::Switch(i, cases) => {
+          let case = match state.conds.get(i as usize) {
+            Some(CondResult::Number(Some(u))) => {
+              cases.iter().find(|c| c.value == *u)
+            }
+            _ => None,
+          };
 
-            match tmp {
-              None => {
-                return SkipResult {
-                  skip_end: Some(lineidx),
-                  ..Default::default()
-                };
-              }
-              Some((i, l)) => {
-                previdx = Some(lineidx);
-                line = l;
-                lineidx = i;
-                linekw = Keyword::parse(l);
-              }
+          if let res @ Some(_) = self.walk_case(case, state, f) {
+            return res;
+          }
+        }
+        Line::RepeatGroup(ref group, i) => {
+          let num = match state.conds.get(i as usize) {
+            Some(CondResult::Number(Some(u))) => *u,
+            _ => continue,
+          };
+
+          for _ in 0..num {
+            if state.linekw.is_some() {
+              return Some(state.result());
+            } else if let res @ Some(_) =
+              self.walk_lines(group.lines, state, f)
+            {
+              return res;
             }
           }
         }
+        Line::RepeatUntil(ref group, end) => loop {
+          let line = state.line.as_ref().trim_ascii_start();
+          if line.starts_with(end.as_bytes()) {
+            if let res @ Some(_) = self.advance(state, cardline, f) {
+              return res;
+            }
+            break;
+          } else if state.linekw.is_some() {
+            return Some(state.result());
+          }
+
+          let before = state.lineidx;
+          if let res @ Some(_) = self.walk_lines(group.lines, state, f) {
+            return res;
+          }
+          // The group did not match the line, don't loop forever
+          if state.lineidx == before {
+            break;
+          }
+        },
+        Line::Select(ref cond, cases) => {
+          if state.linekw.is_some() {
+            return Some(state.result());
+          }
+
+          let case = match cond.evaluate(state.line) {
+            CondResult::Number(Some(u)) => cases.iter().find(|c| c.value == u),
+            _ => None,
+          };
+
+          if let res @ Some(_) = self.walk_case(case, state, f) {
+            return res;
+          }
+        }
+        Line::Block(end) => {
+          while !state.line.as_ref().starts_with(end.as_bytes()) {
+            if state.linekw.is_some() {
+              return Some(state.result());
+            } else if let res @ Some(_) = self.advance(state, cardline, f) {
+              return res;
+            }
+          }
+
+          if let res @ Some(_) = self.advance(state, cardline, f) {
+            return res;
+          }
+        }
       }
     }
-    SkipResult {
-      nextline: Some((lineidx, line)),
-      nextline_kw: linekw,
-      skip_end: previdx,
+    None
+  }
+
+  /// Walk through the lines of the `case` chosen by a
+  /// [`Switch`](::card::line::Line::Switch) or a
+  /// [`Select`](::card::line::Line::Select), if there is one. See
+  /// [`walk_lines`](NoCommentIter::walk_lines).
+  fn walk_case<'b, F>(
+    &'b mut self,
+    case: Option<&'static Case>,
+    state: &mut CardState<'a, T>,
+    f: &mut F,
+  ) -> Option<SkipResult<'a, T>>
+  where
+    F: FnMut(usize, &'a T, &'static Line, Option<&'static Case>),
+  {
+    let case = case?;
+    let outer = state.case.replace(case);
+    let res = self.walk_lines(case.lines, state, f);
+    state.case = outer;
+    res
+  }
+
+  /// Mark the current line of the card as belonging to `cardline`, and
+  /// advance to the next line. Returns `Some` if the file ended.
+  fn advance<'b, F>(
+    &'b mut self,
+    state: &mut CardState<'a, T>,
+    cardline: &'static Line,
+    f: &mut F,
+  ) -> Option<SkipResult<'a, T>>
+  where
+    F: FnMut(usize, &'a T, &'static Line, Option<&'static Case>),
+  {
+    f(state.lineidx, state.line, cardline, state.case);
+
+    match self.next() {
+      None => Some(SkipResult {
+        skip_end: Some(state.lineidx),
+        ..Default::default()
+      }),
+      Some((i, l)) => {
+        state.previdx = Some(state.lineidx);
+        state.line = l;
+        state.lineidx = i;
+        state.linekw = Keyword::parse(l);
+        None
+      }
     }
   }
 
@@ -398,7 +600,7 @@ where
 
     let card: &Card = match nextline.nextline_kw {
       None => unreachable!(),
-      Some(ref k) => k.into(),
+      Some(ref k) => self.card(k),
     };
 
     res = self.skip_card(nextline);
@@ -444,7 +646,7 @@ mod tests {
   use nocommentiter::CommentLess;
   use skipresult::SkipResult;
 
-  const COMMENTS: [&'static str; 8] = [
+  const COMMENTS: [&str; 8] = [
     "#This", "#is", "#an", "#example", "of", "some", "lines", ".",
   ];
 
@@ -467,7 +669,7 @@ mod tests {
     assert_eq!(li.next(), Some((1, &v[1])));
   }
 
-  const KEYWORD_LINES: [&'static str; 8] = [
+  const KEYWORD_LINES: [&str; 8] = [
     "#Comment",
     "   nokeyword",
     "NODE  / ",
@@ -501,7 +703,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const GES1: [&'static str; 5] = [
+  const GES1: [&str; 5] = [
     "        PART 1234",
     "        OGRP 'hausbau'",
     "        DELGRP>NOD 'nix'",
@@ -518,7 +720,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const GES2: [&'static str; 9] = [
+  const GES2: [&str; 9] = [
     "        PART 1234",
     "        OGRP 'hausbau'",
     "        END",
@@ -540,7 +742,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const GES3: [&'static str; 9] = [
+  const GES3: [&str; 9] = [
     "        PART 1234",
     "        OGRP 'hausbau'",
     "NODE  /         END",
@@ -562,7 +764,7 @@ mod tests {
     assert_eq!(li.next(), Some((8, &GES3[8])));
   }
 
-  const GES4: [&'static str; 2] = ["wupdiwup", "NODE  / "];
+  const GES4: [&str; 2] = ["wupdiwup", "NODE  / "];
 
   #[test]
   fn ges_can_skip_nothing() {
@@ -573,7 +775,7 @@ mod tests {
     assert_eq!(li.next(), Some((1, &GES4[1])));
   }
 
-  const GES6: [&'static str; 7] = [
+  const GES6: [&str; 7] = [
     "        PART 1234",
     "#Comment here",
     "        OGRP 'hausbau'",
@@ -594,7 +796,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const GES7: [&'static str; 4] = [
+  const GES7: [&str; 4] = [
     "#        PART 1234",
     "#Comment here",
     "$Another comment",
@@ -612,7 +814,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const GES8: [&'static str; 4] = [
+  const GES8: [&str; 4] = [
     "        PART 1234",
     "#Comment here",
     "$Another comment",
@@ -630,7 +832,7 @@ mod tests {
     assert_eq!(li.next(), None);
   }
 
-  const CARD_NSMAS: [&'static str; 7] = [
+  const CARD_NSMAS: [&str; 7] = [
     "NSMAS /        1              0.                                                ",
     "$#                                                                         TITLE",
     "NAME NSMAS / ->1                                                                ",
@@ -656,7 +858,7 @@ mod tests {
     assert_eq!(tmp.skip_end, Some(5));
   }
 
-  const CARD_NODES: [&'static str; 9] = [
+  const CARD_NODES: [&str; 9] = [
     "NODE  /       28     30.29999924            50.5              0.",
     "NODE  /       28     30.29999924            50.5              0.",
     "NODE  /       28     30.29999924            50.5              0.",
@@ -684,7 +886,7 @@ mod tests {
     assert_eq!(tmp.skip_end, Some(7));
   }
 
-  const CARD_MASS_INCOMPLETE: [&'static str; 9] = [
+  const CARD_MASS_INCOMPLETE: [&str; 9] = [
     "$ MASS Card",
     "$#         IDNOD    IFRA   Blank            DISr            DISs            DISt",
     "MASS  /        0       0                                                        ",
@@ -716,7 +918,7 @@ mod tests {
     assert_eq!(tmp.skip_end, Some(4));
   }
 
-  const CARD_MASS_OPT: [&'static str; 12] = [
+  const CARD_MASS_OPT: [&str; 12] = [
     "MASS  /        0       0                                                        ",
     "$#                                                                         TITLE",
     "NAME MASS  / ->1                                                                ",
@@ -747,7 +949,193 @@ mod tests {
     assert_eq!(tmp.skip_end, Some(10));
   }
 
-  const LINES_GATHER: [&'static str; 20] = [
+  #[test]
+  fn itr_parses_cards() {
+    use card::cell::Value;
+    use card::line::Line;
+
+    let mut li = CARD_MASS_OPT.iter().enumerate().remove_comments();
+    let firstline = li.next().unwrap();
+    let kw = Keyword::parse(&firstline.1);
+    let sr = SkipResult {
+      nextline: Some((firstline.0, firstline.1)),
+      nextline_kw: kw,
+      skip_end: None,
+    };
+
+    let (tmp, parsed) = li.parse_card(&sr);
+    assert_eq!(tmp.nextline, None);
+    assert_eq!(tmp.skip_end, Some(10));
+
+    let indices: Vec<usize> = parsed.iter().map(|l| l.index).collect();
+    assert_eq!(vec![0, 2, 4, 6, 7, 8, 9, 10], indices);
+    assert_eq!(Value::Cont, parsed[3].cells[5].value);
+    match *parsed[6].line {
+      Line::Ges(_) => assert!(parsed[6].cells.is_empty()),
+      _ => panic!("Line 9 should belong to a GES"),
+    }
+  }
+
+  const CARD_SELECT: [&str; 7] = [
+    "NODE  /        1",
+    "       2       3       4",
+    "              1.",
+    "B      text",
+    "NODE  /        1",
+    "       1              5.",
+    "A                  6.",
+  ];
+
+  #[test]
+  fn itr_selects_layouts_by_line() {
+    use card::cell::Cell::*;
+    use card::cell::Value;
+    use card::keyword::Keyword::Node;
+    use card::line::Conditional::*;
+    use card::line::Line::*;
+    use card::line::{Case, Line};
+    use card::Card;
+
+    static SELECT: Card = Card {
+      lines: &[
+        Cells(&[Kw, Integer(8)]),
+        Select(
+          Number(0..8),
+          &[
+            Case {
+              value: 1,
+              lines: &[Cells(&[Integer(8), Float(16)])],
+              fields: &[],
+            },
+            Case {
+              value: 2,
+              lines: &[
+                Cells(&[Integer(8), Integer(8), Integer(8)]),
+                Cells(&[Float(16)]),
+              ],
+              fields: &[],
+            },
+          ],
+        ),
+        Select(
+          Char(0),
+          &[
+            Case {
+              value: 'A' as usize,
+              lines: &[Cells(&[Fixed("A"), Float(20)])],
+              fields: &[],
+            },
+            Case {
+              value: 'B' as usize,
+              lines: &[Cells(&[Fixed("B"), Str(10)])],
+              fields: &[],
+            },
+          ],
+        ),
+      ],
+      fields: &[],
+      ownfold: true,
+      keyword: Node,
+    };
+
+    let parsed = SELECT.parse(&CARD_SELECT);
+    let lines: Vec<&Line> = parsed.iter().map(|p| p.line).collect();
+    let cases = |i: usize| match SELECT.lines[i] {
+      Select(_, cases) => cases,
+      _ => unreachable!(),
+    };
+
+    assert_eq!(4, parsed.len());
+    assert_eq!(&cases(1)[1].lines[0], lines[1]);
+    assert_eq!(&cases(1)[1].lines[1], lines[2]);
+    assert_eq!(&cases(2)[1].lines[0], lines[3]);
+    assert_eq!(Value::Integer(4), parsed[1].cells[2].value);
+
+    let parsed = SELECT.parse(&CARD_SELECT[4..]);
+    assert_eq!(3, parsed.len());
+    assert_eq!(Value::Float(5.), parsed[1].cells[1].value);
+    assert_eq!(Value::Float(6.), parsed[2].cells[1].value);
+  }
+
+  const CARD_REPEATED: [&str; 12] = [
+    "NODE  /        2",
+    "       1              1.",
+    "first",
+    "$ comment",
+    "       2              2.",
+    "second",
+    "       1       2",
+    "       3       4",
+    "        END",
+    "NODE  /        1",
+    "       1              1.",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  #[test]
+  fn itr_repeats_line_groups() {
+    use card::cell::Cell::*;
+    use card::keyword::Keyword::Node;
+    use card::line::Conditional::*;
+    use card::line::Line::*;
+    use card::line::LineGroup;
+    use card::Card;
+    use std::ptr;
+
+    static REPEATED: Card = Card {
+      lines: &[
+        Provides(&[Kw, Integer(8)], Number(8..16)),
+        RepeatGroup(
+          LineGroup {
+            lines: &[Cells(&[Integer(8), Float(16)]), Cells(&[Str(24)])],
+            fields: &[],
+          },
+          0,
+        ),
+        RepeatUntil(
+          LineGroup {
+            lines: &[Cells(&[Integer(8), Integer(8)])],
+            fields: &[],
+          },
+          "END",
+        ),
+      ],
+      fields: &[],
+      ownfold: true,
+      keyword: Node,
+    };
+
+    let mut li = CARD_REPEATED.iter().enumerate().remove_comments();
+    let firstline = li.next().unwrap();
+    let sr = SkipResult {
+      nextline: Some(firstline),
+      nextline_kw: Keyword::parse(&firstline.1),
+      skip_end: None,
+    };
+
+    let (tmp, parsed) = li.parse_card_as(&REPEATED, &sr);
+    let indices: Vec<usize> = parsed.iter().map(|l| l.index).collect();
+    assert_eq!(vec![0, 1, 2, 4, 5, 6, 7, 8], indices);
+    assert_eq!(Some(8), tmp.skip_end);
+    assert_eq!(Some(9), tmp.nextline.map(|(i, _)| i));
+    assert_eq!(&REPEATED.lines[2], parsed[7].line);
+    match REPEATED.lines[1] {
+      RepeatGroup(ref g, _) => {
+        let v: Vec<bool> = parsed[1..5]
+          .iter()
+          .map(|p| ptr::eq(p.line, &g.lines[0]))
+          .collect();
+        assert_eq!(vec![true, false, true, false], v);
+      }
+      _ => unreachable!(),
+    }
+
+    let (tmp, parsed) = li.parse_card_as(&REPEATED, &tmp);
+    assert_eq!(2, parsed.len());
+    assert_eq!(Some(10), tmp.skip_end);
+  }
+
+  const LINES_GATHER: [&str; 20] = [
     /* 0 */
     "NODE  /        1              0.             0.5              0.",
     /* 1 */
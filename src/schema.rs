@@ -0,0 +1,792 @@
+//! This module allows to define additional cards in a TOML file, which is
+//! loaded at startup. The cards are added to a global registry, and
+//! [`Keyword::parse`](::card::keyword::Keyword::parse) returns a
+//! [`Keyword::Custom`](::card::keyword::Keyword::Custom) for lines starting
+//! with their keyword.
+//!
+//! A schema file looks like this:
+//!
+//! ```text
+//! [[card]]
+//! keyword = "MYCARD/"
+//! ownfold = true
+//!
+//! [[card.line]]
+//! cells = ["Kw", "Integer(8)", "Float(16)"]
+//! fields = [
+//!   { name = "", description = "Keyword" },
+//!   { name = "ID", description = "ID of the card" },
+//!   { name = "X", description = "X coordinate", unit = "length" },
+//! ]
+//!
+//! [[card.line]]
+//! cells = ["Fixed(NAME)", "Str(76)"]
+//!
+//! [[card.line]]
+//! cells = ["Blank(8)", "Float(16)", "Blank(55)", "Cont"]
+//! provides = "RelChar(79, &)"
+//!
+//! [[card.line]]
+//! cells = ["Blank(8)", "Float(16)"]
+//! optional = 0
+//!
+//! [[card.line]]
+//! ges = "Node"
+//! ```
+//!
+//! Free-form lines up to a terminator are given by `block = "END_MYCARD"`.
+//!
+//! Alternative layouts are given by a line with `switch = 0`, selecting by
+//! the result of the conditional with index 0, or `select = "Char(0)"`,
+//! selecting by a conditional evaluated on the current line. The layouts are
+//! given by its cases:
+//!
+//! ```text
+//! [[card.line]]
+//! switch = 0
+//!
+//! [[card.line.case]]
+//! value = 1
+//!
+//! [[card.line.case.line]]
+//! cells = ["Blank(8)", "Float(16)"]
+//! ```
+//!
+//! A case of a `Char` conditional can give `char = "A"` instead of a
+//! `value`.
+//!
+//! A group of lines is repeated by a line with `repeat = 0`, like a single
+//! line, or with `until = "END"` to repeat it until a line starting with the
+//! terminator:
+//!
+//! ```text
+//! [[card.line]]
+//! until = "END"
+//!
+//! [[card.line.line]]
+//! cells = ["Integer(8)"]
+//!
+//! [[card.line.line]]
+//! cells = ["Float(16)"]
+//! ```
+//!
+//! The lines of a case or a repeated group can not provide conditionals.
+//!
+//! Cells and conditionals are written like in the [`carddata`](::carddata)
+//! module. `subtype` can be given to define a card by the entry in columns
+//! 17 to 24 (like the types of `PART`). Cards of the [`carddata`](::carddata)
+//! module can not be redefined, their keywords are always matched first.
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use failure::{err_msg, Error, ResultExt};
+use toml;
+
+use card::cell::Cell;
+use card::field::Field;
+use card::ges::GesType;
+use card::keyword::Keyword;
+use card::line::{Case, Conditional, Line, LineGroup};
+use card::Card;
+
+/// The cards of the schema file, together with the strings to recognize them
+struct CustomCard {
+  keyword: [u8; 8],
+  subtype: Option<[u8; 8]>,
+  card: &'static Card,
+}
+
+/// The global registry of cards loaded from a schema file
+static REGISTRY: OnceLock<Vec<CustomCard>> = OnceLock::new();
+
+#[derive(Deserialize)]
+struct SchemaFile {
+  #[serde(default, rename = "card")]
+  cards: Vec<CardSchema>,
+}
+
+#[derive(Deserialize)]
+struct CardSchema {
+  keyword: String,
+  subtype: Option<String>,
+  #[serde(default)]
+  ownfold: bool,
+  #[serde(default, rename = "line")]
+  lines: Vec<LineSchema>,
+}
+
+#[derive(Deserialize)]
+struct LineSchema {
+  #[serde(default)]
+  cells: Vec<String>,
+  ges: Option<String>,
+  block: Option<String>,
+  provides: Option<String>,
+  optional: Option<u8>,
+  repeat: Option<u8>,
+  switch: Option<u8>,
+  select: Option<String>,
+  #[serde(default, rename = "case")]
+  cases: Vec<CaseSchema>,
+  until: Option<String>,
+  #[serde(default, rename = "line")]
+  lines: Vec<LineSchema>,
+  #[serde(default)]
+  fields: Vec<FieldSchema>,
+}
+
+#[derive(Deserialize)]
+struct CaseSchema {
+  value: Option<usize>,
+  #[serde(rename = "char")]
+  chr: Option<char>,
+  #[serde(default, rename = "line")]
+  lines: Vec<LineSchema>,
+}
+
+#[derive(Deserialize)]
+struct FieldSchema {
+  name: String,
+  #[serde(default)]
+  description: String,
+  unit: Option<String>,
+  default: Option<String>,
+}
+
+/// Read the schema file at `path` and [`register`](::schema::register) the
+/// cards it defines.
+pub fn load_file<P: AsRef<Path>>(path: P) -> Result<usize, Error> {
+  let path = path.as_ref();
+  let contents = fs::read_to_string(path)
+    .with_context(|_| format!("Could not read '{}'", path.display()))?;
+
+  register(&contents)
+}
+
+/// Parse a schema and add its cards to the global registry. Can only be
+/// called once, since the cards are referenced by index from
+/// [`Keyword::Custom`](::card::keyword::Keyword::Custom). Returns the number
+/// of cards registered.
+pub fn register(schema: &str) -> Result<usize, Error> {
+  let cards = parse(schema)?;
+  let len = cards.len();
+
+  REGISTRY
+    .set(cards)
+    .map_err(|_| err_msg("Card schema already loaded"))?;
+
+  Ok(len)
+}
+
+/// Return the custom keyword of a line, if the line starts with the keyword
+/// of a card of the registry.
+pub fn keyword(s: &[u8]) -> Option<Keyword> {
+  let cards = REGISTRY.get()?;
+
+  if s.len() < 8 {
+    return None;
+  }
+
+  cards
+    .iter()
+    .find(|c| {
+      s[0..8] == c.keyword
+        && match c.subtype {
+          None => true,
+          Some(ref sub) => s.len() >= 24 && s[16..24] == *sub,
+        }
+    }).map(|c| c.card.keyword)
+}
+
+/// The [`Card`](::card::Card) for a
+/// [`Keyword::Custom`](::card::keyword::Keyword::Custom). Panics if the index
+/// is not in the registry, which can't happen for a keyword returned by
+/// [`keyword`](::schema::keyword).
+pub fn card(idx: u16) -> &'static Card {
+  REGISTRY.get().expect("No card schema loaded")[idx as usize].card
+}
+
+/// Parse a schema file into the cards of the registry
+fn parse(schema: &str) -> Result<Vec<CustomCard>, Error> {
+  let file: SchemaFile =
+    toml::from_str(schema).context("Could not parse card schema")?;
+
+  if file.cards.len() > usize::from(u16::MAX) {
+    return Err(err_msg("Too many cards in schema"));
+  }
+
+  file
+    .cards
+    .into_iter()
+    .enumerate()
+    .map(|(i, c)| {
+      let kw = c.keyword.clone();
+      to_custom(i as u16, c)
+        .with_context(|_| format!("Invalid definition of card '{}'", kw))
+        .map_err(Error::from)
+    }).collect()
+}
+
+/// Turn the definition of a card into a static
+/// [`Card`](::card::Card). The memory is leaked, since the cards are needed
+/// until the program exits.
+fn to_custom(idx: u16, schema: CardSchema) -> Result<CustomCard, Error> {
+  let keyword = pad(&schema.keyword, false)?;
+  let subtype = match schema.subtype {
+    Some(ref s) => Some(pad(s, true)?),
+    None => None,
+  };
+
+  let (lines, fields) = to_lines(&schema.lines, 0, false)?;
+
+  match lines.first() {
+    None => return Err(err_msg("A card needs at least one line")),
+    Some(l) if l.cells().first() != Some(&Cell::Kw) => {
+      return Err(err_msg("The first line needs to start with 'Kw'"))
+    }
+    Some(_) => {}
+  }
+
+  let card: &'static Card = Box::leak(Box::new(Card {
+    lines,
+    fields,
+    ownfold: schema.ownfold,
+    keyword: Keyword::Custom(idx),
+  }));
+
+  Ok(CustomCard {
+    keyword,
+    subtype,
+    card,
+  })
+}
+
+/// Pad a keyword to 8 characters, either on the left or the right side
+fn pad(s: &str, left: bool) -> Result<[u8; 8], Error> {
+  if s.len() > 8 || !s.is_ascii() {
+    return Err(err_msg(format!(
+      "'{}' needs to consist of at most 8 ASCII characters",
+      s
+    )));
+  }
+
+  let padded = if left {
+    format!("{:>8}", s)
+  } else {
+    format!("{:<8}", s)
+  };
+  let mut res = [b' '; 8];
+  res.copy_from_slice(padded.as_bytes());
+
+  Ok(res)
+}
+
+/// Turn the definitions of lines into [`Line`](::card::line::Line)s and
+/// their [`Field`](::card::field::Field)s. `num_conds` is the number of
+/// conditionals provided before the lines. `nested` lines belong to a
+/// [`Case`](::card::line::Case) or a [`LineGroup`](::card::line::LineGroup),
+/// and can not provide conditionals, since the indices of later
+/// conditionals would depend on the case or the number of repetitions.
+fn to_lines(
+  schemas: &[LineSchema],
+  mut num_conds: u8,
+  nested: bool,
+) -> Result<(&'static [Line], &'static [&'static [Field]]), Error> {
+  let mut lines = Vec::with_capacity(schemas.len());
+  let mut fields = Vec::with_capacity(schemas.len());
+
+  for (i, l) in schemas.iter().enumerate() {
+    let line = to_line(l, num_conds)
+      .with_context(|_| format!("Invalid line {}", i + 1))?;
+
+    if !l.fields.is_empty() && l.fields.len() != line.cells().len() {
+      return Err(err_msg(format!(
+        "Line {} has {} cells, but {} fields",
+        i + 1,
+        line.cells().len(),
+        l.fields.len()
+      )));
+    }
+    if let Line::Provides(..) = line {
+      if nested {
+        return Err(err_msg(format!(
+          "Line {} can not provide a conditional inside a case or group",
+          i + 1
+        )));
+      }
+      num_conds += 1;
+    }
+
+    fields.push(leak_slice(l.fields.iter().map(to_field).collect()));
+    lines.push(line);
+  }
+
+  Ok((leak_slice(lines), leak_slice(fields)))
+}
+
+/// Turn the definition of a line into a [`Line`](::card::line::Line).
+/// `num_conds` is the number of conditionals provided by the lines before.
+fn to_line(l: &LineSchema, num_conds: u8) -> Result<Line, Error> {
+  let check_idx = |i: u8| {
+    if i < num_conds {
+      Ok(i)
+    } else {
+      Err(err_msg(format!("No conditional with index {}", i)))
+    }
+  };
+
+  if let Some(ref g) = l.ges {
+    if !l.cells.is_empty() {
+      return Err(err_msg("A GES can not have cells"));
+    }
+    let ges = match g.as_ref() {
+      "Node" => GesType::GesNode,
+      "Ele" => GesType::GesEle,
+      "Edge" => GesType::GesEdge,
+      "Face" => GesType::GesFace,
+      _ => return Err(err_msg(format!("Unknown GES type '{}'", g))),
+    };
+    return Ok(Line::Ges(ges));
+  }
+
+  if let Some(ref b) = l.block {
+    if !l.cells.is_empty() || b.is_empty() {
+      return Err(err_msg("A block needs a terminator, and no cells"));
+    }
+    return Ok(Line::Block(leak_str(b.clone())));
+  }
+
+  if !l.lines.is_empty() || l.until.is_some() {
+    if !l.cells.is_empty() || l.lines.is_empty() {
+      return Err(err_msg("A repeated group needs lines, and no cells"));
+    }
+    let (lines, fields) = to_lines(&l.lines, num_conds, true)?;
+    let group = LineGroup { lines, fields };
+
+    return match (l.repeat, l.until.as_ref()) {
+      (Some(i), None) => Ok(Line::RepeatGroup(group, check_idx(i)?)),
+      (None, Some(u)) if !u.is_empty() => {
+        Ok(Line::RepeatUntil(group, leak_str(u.clone())))
+      }
+      _ => Err(err_msg("A repeated group needs either 'repeat' or 'until'")),
+    };
+  }
+
+  if l.switch.is_some() || l.select.is_some() {
+    if !l.cells.is_empty() || l.cases.is_empty() {
+      return Err(err_msg("A switch or select needs cases, and no cells"));
+    }
+    let cases = l
+      .cases
+      .iter()
+      .enumerate()
+      .map(|(i, c)| {
+        to_case(c, num_conds)
+          .with_context(|_| format!("Invalid case {}", i + 1))
+          .map_err(Error::from)
+      }).collect::<Result<Vec<_>, _>>()?;
+    let cases = leak_slice(cases);
+
+    return match (l.switch, l.select.as_ref()) {
+      (Some(i), None) => Ok(Line::Switch(check_idx(i)?, cases)),
+      (None, Some(s)) => match to_conditional(s)? {
+        c @ Conditional::Number(_) | c @ Conditional::Char(_) => {
+          Ok(Line::Select(c, cases))
+        }
+        _ => Err(err_msg("A select needs a 'Number' or 'Char' conditional")),
+      },
+      _ => Err(err_msg("Only one of 'switch' and 'select' can be given")),
+    };
+  }
+
+  let cells = leak_slice(
+    l.cells
+      .iter()
+      .map(|c| to_cell(c))
+      .collect::<Result<Vec<_>, _>>()?,
+  );
+
+  match (l.provides.as_ref(), l.optional, l.repeat) {
+    (None, None, None) => Ok(Line::Cells(cells)),
+    (Some(p), None, None) => Ok(Line::Provides(cells, to_conditional(p)?)),
+    (None, Some(i), None) => Ok(Line::Optional(cells, check_idx(i)?)),
+    (None, None, Some(i)) => Ok(Line::Repeat(cells, check_idx(i)?)),
+    _ => Err(err_msg(
+      "Only one of 'provides', 'optional' and 'repeat' can be given",
+    )),
+  }
+}
+
+/// Turn the definition of a case into a [`Case`](::card::line::Case). The
+/// value is either given as a number, or as a char for a
+/// [`Char`](::card::line::Conditional::Char) conditional.
+fn to_case(c: &CaseSchema, num_conds: u8) -> Result<Case, Error> {
+  let value = match (c.value, c.chr) {
+    (Some(v), None) => v,
+    (None, Some(c)) => c as usize,
+    _ => return Err(err_msg("A case needs either a 'value' or a 'char'")),
+  };
+  let (lines, fields) = to_lines(&c.lines, num_conds, true)?;
+
+  Ok(Case {
+    value,
+    lines,
+    fields,
+  })
+}
+
+/// Split an entry like `Integer(8)` into the name and the arguments
+fn split_args(s: &str) -> Result<(&str, Vec<&str>), Error> {
+  let s = s.trim();
+
+  match s.find('(') {
+    None => Ok((s, vec![])),
+    Some(i) if s.ends_with(')') => {
+      Ok((&s[..i], s[i + 1..s.len() - 1].split(',').collect()))
+    }
+    Some(_) => Err(err_msg(format!("Missing ')' in '{}'", s))),
+  }
+}
+
+/// Parse a number, with a nice error message
+fn number<T: ::std::str::FromStr>(s: &str) -> Result<T, Error> {
+  s.trim()
+    .parse::<T>()
+    .map_err(|_| err_msg(format!("'{}' is not a valid number", s.trim())))
+}
+
+/// Parse a range like `25..33`
+fn range(s: &str) -> Result<Range<usize>, Error> {
+  let mut it = s.split("..");
+
+  match (it.next(), it.next(), it.next()) {
+    (Some(a), Some(b), None) => Ok(number(a)?..number(b)?),
+    _ => Err(err_msg(format!("'{}' is not a valid range", s.trim()))),
+  }
+}
+
+/// Parse a cell like `Float(16)` into a [`Cell`](::card::cell::Cell)
+fn to_cell(s: &str) -> Result<Cell, Error> {
+  let (name, args) = split_args(s)?;
+
+  match (name, args.as_slice()) {
+    ("Kw", &[]) => Ok(Cell::Kw),
+    ("Cont", &[]) => Ok(Cell::Cont),
+    ("Fixed", &[f]) => Ok(Cell::Fixed(leak_str(f.trim().to_owned()))),
+    ("Integer", &[u]) => Ok(Cell::Integer(number(u)?)),
+    ("Float", &[u]) => Ok(Cell::Float(number(u)?)),
+    ("Blank", &[u]) => Ok(Cell::Blank(number(u)?)),
+    ("Str", &[u]) => Ok(Cell::Str(number(u)?)),
+    ("Binary", &[u]) => Ok(Cell::Binary(number(u)?)),
+    ("IntegerorBlank", &[u]) => Ok(Cell::IntegerorBlank(number(u)?)),
+    _ => Err(err_msg(format!("Unknown cell '{}'", s))),
+  }
+}
+
+/// Parse a conditional like `Int(25..33, 0)` into a
+/// [`Conditional`](::card::line::Conditional)
+fn to_conditional(s: &str) -> Result<Conditional, Error> {
+  let (name, args) = split_args(s)?;
+
+  match (name, args.as_slice()) {
+    ("RelChar", &[i, c]) => {
+      let mut chars = c.trim().chars();
+      match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(Conditional::RelChar(number(i)?, c)),
+        _ => Err(err_msg(format!("'{}' is not a single char", c.trim()))),
+      }
+    }
+    ("Int", &[r, i]) => Ok(Conditional::Int(range(r)?, number(i)?)),
+    ("Number", &[r]) => Ok(Conditional::Number(range(r)?)),
+    ("Char", &[i]) => Ok(Conditional::Char(number(i)?)),
+    ("Count", &[r, n]) => match number(n)? {
+      0 => Err(err_msg("A count needs at least 1 value per line")),
+      n => Ok(Conditional::Count(range(r)?, n)),
+    },
+    _ => Err(err_msg(format!("Unknown conditional '{}'", s))),
+  }
+}
+
+fn to_field(f: &FieldSchema) -> Field {
+  Field {
+    name: leak_str(f.name.clone()),
+    description: leak_str(f.description.clone()),
+    unit: f.unit.clone().map(leak_str),
+    default: f.default.clone().map(leak_str),
+  }
+}
+
+fn leak_str(s: String) -> &'static str {
+  Box::leak(s.into_boxed_str())
+}
+
+fn leak_slice<T>(v: Vec<T>) -> &'static [T] {
+  Box::leak(v.into_boxed_slice())
+}
+
+#[cfg(test)]
+mod tests {
+  use card::cell::Cell::*;
+  use card::keyword::Keyword;
+  use card::line::Conditional;
+  use card::line::Line;
+  use card::Card;
+  use folds::FoldList;
+  use schema::{parse, register};
+
+  const SCHEMA: &str = r#"
+[[card]]
+keyword = "MYCARD/"
+ownfold = true
+
+[[card.line]]
+cells = ["Kw", "Integer(8)", "Float(16)"]
+fields = [
+  { name = "", description = "Keyword" },
+  { name = "ID", description = "ID of the card" },
+  { name = "X", description = "X coordinate", unit = "length" },
+]
+
+[[card.line]]
+cells = ["Fixed(NAME)", "Str(76)"]
+
+[[card.line]]
+cells = ["Blank(8)", "Float(16)", "Blank(55)", "Cont"]
+provides = "RelChar(79, &)"
+
+[[card.line]]
+cells = ["Blank(8)", "Float(16)"]
+optional = 0
+
+[[card.line]]
+ges = "Node"
+
+[[card]]
+keyword = "PART  /"
+subtype = "MYPRT"
+
+[[card.line]]
+cells = ["Kw", "Integer(8)", "Str(8)"]
+
+[[card.line]]
+cells = ["Fixed(END_PART)"]
+
+[[card]]
+keyword = "MYCTRL/"
+
+[[card.line]]
+cells = ["Kw"]
+
+[[card.line]]
+block = "END_MYCTRL"
+"#;
+
+  #[test]
+  fn schema_can_be_parsed() {
+    let cards = parse(SCHEMA).unwrap();
+    let card = cards[0].card;
+
+    assert_eq!(3, cards.len());
+    assert_eq!(Line::Block("END_MYCTRL"), cards[2].card.lines[1]);
+    assert_eq!(b"MYCARD/ ", &cards[0].keyword);
+    assert_eq!(Some(*b"   MYPRT"), cards[1].subtype);
+    assert_eq!(5, card.lines.len());
+    assert_eq!(Line::Cells(&[Kw, Integer(8), Float(16)]), card.lines[0]);
+    assert_eq!(Line::Cells(&[Fixed("NAME"), Str(76)]), card.lines[1]);
+    assert_eq!(
+      Line::Provides(
+        &[Blank(8), Float(16), Blank(55), Cont],
+        Conditional::RelChar(79, '&')
+      ),
+      card.lines[2]
+    );
+    assert_eq!(Line::Optional(&[Blank(8), Float(16)], 0), card.lines[3]);
+    assert_eq!(Some("length"), card.field(&card.lines[0], 2).unwrap().unit);
+    assert!(card.ownfold);
+  }
+
+  #[test]
+  fn schema_errors_are_reported() {
+    let errors = [
+      "[[card]]\nkeyword = \"TOOLONGKEYWORD\"\n[[card.line]]\ncells=[\"Kw\"]",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Integer(8)\"]",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\", \"Int(8)\"]",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\noptional=0",
+      "[[card]]\nkeyword = \"A\"",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       provides=\"Count(16..24, 0)\"",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nswitch=0\n[[card.line.case]]\nvalue=1",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nselect=\"Int(0..8, 1)\"\n[[card.line.case]]\nvalue=1",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nselect=\"Char(0)\"\n[[card.line.case]]\n\
+       value=1\nchar=\"A\"",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nselect=\"Char(0)\"\n[[card.line.case]]\nchar=\"A\"\n\
+       [[card.line.case.line]]\ncells=[\"Str(8)\"]\nprovides=\"Char(0)\"",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nuntil=\"END\"",
+      "[[card]]\nkeyword = \"A\"\n[[card.line]]\ncells=[\"Kw\"]\n\
+       [[card.line]]\nrepeat=0\n[[card.line.line]]\ncells=[\"Str(8)\"]",
+    ];
+
+    for e in errors.iter() {
+      assert!(parse(e).is_err(), "{}", e);
+    }
+  }
+
+  const SWITCH_SCHEMA: &str = r#"
+[[card]]
+keyword = "MYSWIT/"
+
+[[card.line]]
+cells = ["Kw", "Integer(8)"]
+provides = "Number(8..16)"
+
+[[card.line]]
+switch = 0
+
+[[card.line.case]]
+value = 1
+
+[[card.line.case.line]]
+cells = ["Float(16)"]
+fields = [{ name = "X", unit = "length" }]
+
+[[card.line.case]]
+value = 2
+
+[[card.line]]
+select = "Char(0)"
+
+[[card.line.case]]
+char = "A"
+
+[[card.line.case.line]]
+cells = ["Fixed(A)", "Integer(8)"]
+"#;
+
+  #[test]
+  fn schema_switch_and_select() {
+    let cards = parse(SWITCH_SCHEMA).unwrap();
+    let card = cards[0].card;
+
+    let (cases, sel) = match (&card.lines[1], &card.lines[2]) {
+      (&Line::Switch(0, cases), &Line::Select(Conditional::Char(0), sel)) => {
+        (cases, sel)
+      }
+      l => panic!("Unexpected lines {:?}", l),
+    };
+    let values: Vec<usize> = cases.iter().map(|c| c.value).collect();
+    assert_eq!(vec![1, 2], values);
+    assert_eq!(&[Line::Cells(&[Float(16)])], cases[0].lines);
+    assert!(cases[1].lines.is_empty());
+
+    let x = card.field(&cases[0].lines[0], 0).unwrap();
+    assert_eq!(("X", Some("length")), (x.name, x.unit));
+    assert_eq!('A' as usize, sel[0].value);
+
+    let lines = ["MYSWIT/        1", "             1.5", "A              3"];
+    let parsed = card.parse(&lines);
+    assert_eq!(3, parsed.len());
+    assert!(::std::ptr::eq(&sel[0].lines[0], parsed[2].line));
+  }
+
+  const GROUP_SCHEMA: &str = r#"
+[[card]]
+keyword = "MYGRP /"
+
+[[card.line]]
+cells = ["Kw", "Integer(8)"]
+provides = "Number(8..16)"
+
+[[card.line]]
+repeat = 0
+
+[[card.line.line]]
+cells = ["Integer(8)"]
+
+[[card.line.line]]
+cells = ["Float(16)"]
+fields = [{ name = "X" }]
+
+[[card.line]]
+until = "END"
+
+[[card.line.line]]
+cells = ["Blank(8)", "Integer(8)"]
+"#;
+
+  #[test]
+  fn schema_repeated_groups() {
+    let cards = parse(GROUP_SCHEMA).unwrap();
+    let card = cards[0].card;
+
+    let group = match card.lines[1] {
+      Line::RepeatGroup(ref g, 0) => g,
+      ref l => panic!("Unexpected line {:?}", l),
+    };
+    assert_eq!(&[Line::Cells(&[Integer(8)])], &group.lines[..1]);
+    assert_eq!(Some("X"), card.field(&group.lines[1], 0).map(|f| f.name));
+    match card.lines[2] {
+      Line::RepeatUntil(ref g, "END") => assert_eq!(1, g.lines.len()),
+      ref l => panic!("Unexpected line {:?}", l),
+    }
+
+    let lines = [
+      "MYGRP /        2",
+      "       1",
+      "              1.",
+      "       2",
+      "              2.",
+      "               3",
+      "               4",
+      "        END",
+    ];
+    assert_eq!(8, card.parse(&lines).len());
+  }
+
+  const LINES: [&str; 12] = [
+    "MYCARD/        1              1.",
+    "NAME Custom card",
+    "                      2.                                                       &",
+    "                    3.",
+    "        NOD 1",
+    "        END",
+    "PART  /        2   MYPRT",
+    "END_PART",
+    "MYCTRL/ ",
+    " free text",
+    "END_MYCTRL",
+    "NODE  /        1              0.             0.5              0.",
+  ];
+
+  // This is the only test that may touch the global registry
+  #[test]
+  fn custom_cards_are_recognized() {
+    assert_eq!(3, register(SCHEMA).unwrap());
+    assert!(register(SCHEMA).is_err());
+
+    let kw = Keyword::parse(&LINES[0]).unwrap();
+    assert_eq!(Keyword::Custom(0), kw);
+    assert_eq!(Some(Keyword::Custom(1)), Keyword::parse(&LINES[6]));
+    assert_eq!(None, Keyword::parse(&"PART  /        2   OTHER"));
+
+    let card = Card::for_version(&kw, None);
+    assert_eq!(6, card.parse(&LINES).len());
+
+    let mut foldlist = FoldList::new();
+    foldlist.add_folds(&LINES).unwrap();
+    assert_eq!(
+      vec![
+        (0, 5, Keyword::Custom(0)),
+        (6, 7, Keyword::Custom(1)),
+        (8, 10, Keyword::Custom(2)),
+      ],
+      foldlist.into_vec()
+    );
+  }
+}
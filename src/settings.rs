@@ -0,0 +1,207 @@
+//! This module provides [`ModelSettings`](::settings::ModelSettings), the
+//! settings given by the control cards at the top of a deck (`INPUTVERSION`,
+//! `ANALYSIS`, `SOLVER`, `UNIT` and `TITLE`).
+use std::str;
+
+use card::cell::Value;
+use card::keyword::Keyword;
+use card::Card;
+use nocommentiter::CommentLess;
+use skipresult::SkipResult;
+
+/// The units of a model, as given by the `UNIT` card
+#[derive(Clone, Debug, PartialEq)]
+pub struct Units {
+  pub length: String,
+  pub mass: String,
+  pub time: String,
+  pub temperature: String,
+}
+
+/// The settings of a model. Entries are `None` if the corresponding card is
+/// missing, or its entry could not be read. If a card occurs more than once,
+/// the last one wins.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelSettings {
+  pub inputversion: Option<u32>,
+  pub analysis: Option<String>,
+  pub solver: Option<String>,
+  pub units: Option<Units>,
+  pub title: Option<String>,
+}
+
+impl ModelSettings {
+  /// Read the settings from the lines of a buffer
+  pub fn from_lines<T: AsRef<[u8]>>(lines: &[T]) -> ModelSettings {
+    let mut settings = ModelSettings::default();
+    let mut li = lines.iter().enumerate().remove_comments();
+    let mut nextline = SkipResult::from(li.next());
+
+    while let Some((_, l)) = nextline.nextline {
+      nextline = match nextline.nextline_kw {
+        Some(Keyword::Inputversion)
+        | Some(Keyword::Analysis)
+        | Some(Keyword::Solver)
+        | Some(Keyword::Unit)
+        | Some(Keyword::Title) => {
+          if let Ok(l) = str::from_utf8(l.as_ref()) {
+            settings.read(l);
+          }
+          SkipResult::from(li.next())
+        }
+        Some(_) => li.skip_fold(&nextline),
+        None => SkipResult::from(li.next()),
+      };
+    }
+
+    settings
+  }
+
+  /// Update the settings from a line of one of the control cards
+  fn read(&mut self, line: &str) {
+    let kw = match Keyword::parse(&line) {
+      Some(k) => k,
+      None => return,
+    };
+    let card = Card::for_version(&kw, self.inputversion);
+    let cells = card.lines[0].parse(&line);
+    let strs: Vec<Option<String>> = cells
+      .iter()
+      .map(|c| match c.value {
+        Value::Str(s) if !s.trim().is_empty() => Some(s.trim().to_owned()),
+        _ => None,
+      }).collect();
+
+    match kw {
+      Keyword::Inputversion => {
+        self.inputversion = match cells[1].value {
+          Value::Integer(i) if i >= 0 => Some(i as u32),
+          _ => None,
+        }
+      }
+      Keyword::Analysis => self.analysis = strs[1].clone(),
+      Keyword::Solver => self.solver = strs[1].clone(),
+      Keyword::Title => self.title = strs[1].clone(),
+      Keyword::Unit => {
+        self.units = match (&strs[1], &strs[2], &strs[3], &strs[4]) {
+          (Some(l), Some(m), Some(t), Some(temp)) => Some(Units {
+            length: l.clone(),
+            mass: m.clone(),
+            time: t.clone(),
+            temperature: temp.clone(),
+          }),
+          _ => None,
+        }
+      }
+      _ => {}
+    }
+  }
+
+  /// The concrete unit of a dimension like `"force/length^2"`, as used in
+  /// the [`unit`](::card::field::Field) of a field. Returns `None` if the
+  /// model has no units, or the dimension contains an unknown quantity.
+  pub fn unit(&self, dimension: &str) -> Option<String> {
+    let units = self.units.as_ref()?;
+    let mut unit = String::with_capacity(dimension.len());
+    let mut rest = dimension;
+
+    while let Some(start) = rest.find(char::is_alphabetic) {
+      unit.push_str(&rest[..start]);
+      rest = &rest[start..];
+
+      let end = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+
+      match &rest[..end] {
+        "length" => unit.push_str(&units.length),
+        "mass" => unit.push_str(&units.mass),
+        "time" => unit.push_str(&units.time),
+        "temperature" => unit.push_str(&units.temperature),
+        "force" => unit.push_str(&format!(
+          "({}*{}/{}^2)",
+          units.mass, units.length, units.time
+        )),
+        _ => return None,
+      }
+      rest = &rest[end..];
+    }
+    unit.push_str(rest);
+
+    Some(unit)
+  }
+}
+
+/// The values Pamcrash accepts in the cell with index `cell` of the control
+/// card `kw`. `None` if the values of the cell are not restricted.
+pub fn allowed_values(
+  kw: Keyword,
+  cell: usize,
+) -> Option<&'static [&'static str]> {
+  match (kw, cell) {
+    (Keyword::Analysis, 1) => Some(&["EXPLICIT", "IMPLICIT"]),
+    (Keyword::Solver, 1) => Some(&["CRASH", "SAFE"]),
+    (Keyword::Unit, 1) => Some(&["MM", "CM", "M"]),
+    (Keyword::Unit, 2) => Some(&["G", "KG", "TON"]),
+    (Keyword::Unit, 3) => Some(&["MS", "S"]),
+    (Keyword::Unit, 4) => Some(&["KELVIN", "CELSIUS"]),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use settings::{ModelSettings, Units};
+
+  const LINES: [&str; 14] = [
+    "INPUTVERSION 2011",
+    "ANALYSIS EXPLICIT    ",
+    "SOLVER    CRASH",
+    "$",
+    "UNIT       MM       KG       MS   KELVIN",
+    "SIGNAL      YES",
+    "$",
+    "TITLE /  BoxBeam fine meshed model                                            ",
+    "RUNEND/  ",
+    " TIME      15.01",
+    "END_RUNEND",
+    "NODE  /        1              0.             0.5              0.",
+    "NODE  /        2              0.             0.5              0.",
+    "TITLE /  Not the first title",
+  ];
+
+  #[test]
+  fn settings_can_be_read() {
+    let settings = ModelSettings::from_lines(&LINES);
+
+    assert_eq!(
+      ModelSettings {
+        inputversion: Some(2011),
+        analysis: Some("EXPLICIT".to_owned()),
+        solver: Some("CRASH".to_owned()),
+        units: Some(Units {
+          length: "MM".to_owned(),
+          mass: "KG".to_owned(),
+          time: "MS".to_owned(),
+          temperature: "KELVIN".to_owned(),
+        }),
+        title: Some("Not the first title".to_owned()),
+      },
+      settings
+    );
+    assert_eq!(ModelSettings::default(), ModelSettings::from_lines(&[""]));
+  }
+
+  #[test]
+  fn units_can_be_derived() {
+    let settings = ModelSettings::from_lines(&LINES);
+
+    assert_eq!(Some("MM".to_owned()), settings.unit("length"));
+    assert_eq!(Some("KG/MM^3".to_owned()), settings.unit("mass/length^3"));
+    assert_eq!(
+      Some("(KG*MM/MS^2)/MM^2".to_owned()),
+      settings.unit("force/length^2")
+    );
+    assert_eq!(Some("1/MS".to_owned()), settings.unit("1/time"));
+    assert_eq!(None, settings.unit("charge"));
+    assert_eq!(None, ModelSettings::default().unit("length"));
+  }
+}
@@ -16,7 +16,7 @@ use card::keyword::Keyword;
 /// we could not fully skip something before the file ended
 pub struct SkipResult<'a, T: 'a>
 where
-  T: AsRef<str>,
+  T: AsRef<[u8]>,
 {
   pub nextline: Option<(usize, &'a T)>,
   pub nextline_kw: Option<Keyword>,
@@ -25,7 +25,7 @@ where
 
 impl<'a, T: 'a> Default for SkipResult<'a, T>
 where
-  T: AsRef<str>,
+  T: AsRef<[u8]>,
 {
   fn default() -> Self {
     SkipResult {
@@ -36,9 +36,24 @@ where
   }
 }
 
+impl<'a, T: 'a> From<Option<(usize, &'a T)>> for SkipResult<'a, T>
+where
+  T: AsRef<[u8]>,
+{
+  /// Create a `SkipResult` from a line returned by the iterator, without
+  /// having skipped anything
+  fn from(nextline: Option<(usize, &'a T)>) -> Self {
+    SkipResult {
+      nextline,
+      nextline_kw: nextline.and_then(|(_, l)| Keyword::parse(l)),
+      skip_end: None,
+    }
+  }
+}
+
 impl<'a, T: 'a> fmt::Debug for SkipResult<'a, T>
 where
-  T: AsRef<str>,
+  T: AsRef<[u8]>,
 {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self.nextline {
@@ -52,7 +67,7 @@ where
         "SkipResult {{ nextline: ({:?}, {:?}), nextline_kw: {:?}, \
          skip_end: {:?} }}",
         n.0,
-        n.1.as_ref(),
+        String::from_utf8_lossy(n.1.as_ref()),
         self.nextline_kw,
         self.skip_end
       ),
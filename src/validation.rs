@@ -0,0 +1,693 @@
+//! This module provides [`validate`](::validation::validate) to check the
+//! lines of a buffer against the definitions of the cards in the
+//! [`carddata`](::carddata) module, and a way to show the resulting
+//! [`Diagnostic`](::validation::Diagnostic)s in neovim.
+use std::ops::Range;
+use std::ptr;
+use std::str;
+
+use failure::Error;
+use failure::ResultExt;
+use neovim_lib::{Neovim, NeovimApi, Value as NvimValue};
+
+use card::cell::{Cell, Value};
+use card::line::{Case, CondResult, Line, ParsedLine};
+use card::Card;
+use header;
+use include::Model;
+use nocommentiter::CommentLess;
+use settings::{self, ModelSettings};
+use skipresult::SkipResult;
+
+/// The severity of a [`Diagnostic`](::validation::Diagnostic)
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+  /// Pamcrash will reject the deck, or misread it
+  Error,
+  /// Probably a mistake, but Pamcrash might still accept the deck
+  Warning,
+}
+
+/// A problem found in a line of the buffer. `line` is the index of the line
+/// (0-based), `range` the columns that are affected (0-based, end exclusive).
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+  pub line: usize,
+  pub range: Range<usize>,
+  pub severity: Severity,
+  pub message: String,
+}
+
+/// Check all cards in `lines` against their definitions. Lines that do not
+/// belong to any card (and are not empty) are reported, too. The
+/// [`ModelSettings`](::settings::ModelSettings) of `lines` select the layouts
+/// of the cards, and the units shown in the messages.
+pub fn validate<T: AsRef<[u8]>>(lines: &[T]) -> Vec<Diagnostic> {
+  validate_with_settings(lines, &ModelSettings::from_lines(lines))
+}
+
+/// Like [`validate`](::validation::validate), but use the given settings
+/// instead of the ones of `lines`. An `INPUTVERSION` card in `lines` still
+/// changes the layouts from there on. Used for included files, which take
+/// the settings of the including deck.
+pub fn validate_with_settings<T: AsRef<[u8]>>(
+  lines: &[T],
+  settings: &ModelSettings,
+) -> Vec<Diagnostic> {
+  let mut diags = vec![];
+  let mut li = lines
+    .iter()
+    .enumerate()
+    .remove_comments()
+    .with_version(settings.inputversion);
+  let mut nextline = SkipResult::from(li.next());
+
+  loop {
+    match nextline.nextline {
+      None => break,
+      Some((i, l)) => match nextline.nextline_kw {
+        None => {
+          let l = l.as_ref();
+          if !l.trim_ascii().is_empty() {
+            diags.push(Diagnostic {
+              line: i,
+              range: 0..l.len(),
+              severity: Severity::Warning,
+              message: "Line does not belong to any known card".to_owned(),
+            });
+          }
+          nextline = SkipResult::from(li.next());
+        }
+        Some(ref kw) => {
+          let card = li.card(kw);
+          let (res, parsed) = li.parse_card(&nextline);
+          check_card(card, &parsed, lines, settings, &mut diags);
+          nextline = res;
+        }
+      },
+    }
+  }
+
+  diags
+}
+
+/// Check the parsed lines of a card, and add all problems to `diags`.
+fn check_card<T: AsRef<[u8]>>(
+  card: &Card,
+  parsed: &[ParsedLine],
+  lines: &[T],
+  settings: &ModelSettings,
+  diags: &mut Vec<Diagnostic>,
+) {
+  for pl in parsed {
+    let line = lines[pl.index].as_ref();
+    check_cells(card, pl, line, settings, diags);
+    check_allowed(card, pl, diags);
+
+    if pl.index > 0 && !pl.cells.is_empty() {
+      check_header(pl, lines[pl.index - 1].as_ref(), diags);
+    }
+  }
+
+  if let Some(first) = parsed.first() {
+    // The conditionals in the order the walker pushed them
+    let conds: Vec<CondResult> = parsed
+      .iter()
+      .filter_map(|p| match *p.line {
+        Line::Provides(_, ref c) => Some(c.evaluate(&lines[p.index])),
+        _ => None,
+      }).collect();
+    let missing = count_missing(card.lines, parsed, &conds, lines);
+
+    if missing > 0 {
+      diags.push(Diagnostic {
+        line: first.index,
+        range: 0..8,
+        severity: Severity::Error,
+        message: format!("Card is incomplete, {} line(s) missing", missing),
+      });
+    }
+
+    for l in card.lines {
+      // Repetitions may be terminated by an indented line, blocks may not
+      let (what, end, indented) = match *l {
+        Line::Block(end) => ("Block", end, false),
+        Line::RepeatUntil(_, end) => ("Repetition", end, true),
+        _ => continue,
+      };
+      let terminated = parsed.iter().any(|p| {
+        let line = lines[p.index].as_ref();
+        let line = if indented { line.trim_ascii_start() } else { line };
+        ptr::eq(p.line, l) && line.starts_with(end.as_bytes())
+      });
+
+      if !terminated {
+        diags.push(Diagnostic {
+          line: first.index,
+          range: 0..8,
+          severity: Severity::Error,
+          message: format!("{} is not terminated by '{}'", what, end),
+        });
+      }
+    }
+  }
+}
+
+/// Count the [`Cells`](::card::line::Line::Cells) lines of `cardlines` that
+/// are not in `parsed`. For a [`Switch`](::card::line::Line::Switch) or a
+/// [`Select`](::card::line::Line::Select), only the case chosen by its
+/// conditional is checked. The conditional of a `Select` is evaluated on the
+/// first line of its case; if there is none, the lines of the shortest case
+/// are missing. A [`Repeat`](::card::line::Line::Repeat) line, or a line of
+/// a [`RepeatGroup`](::card::line::Line::RepeatGroup), is missing as often
+/// as it occurs less than its conditional demands.
+fn count_missing<T: AsRef<[u8]>>(
+  cardlines: &'static [Line],
+  parsed: &[ParsedLine],
+  conds: &[CondResult],
+  lines: &[T],
+) -> usize {
+  let found = |l: &Line| parsed.iter().any(|p| ptr::eq(p.line, l));
+  let missing_in = |cases: &[Case], n: usize| {
+    cases
+      .iter()
+      .find(|c| c.value == n)
+      .map_or(0, |c| count_missing(c.lines, parsed, conds, lines))
+  };
+
+  cardlines
+    .iter()
+    .map(|l| match *l {
+      Line::Cells(_) if !found(l) => 1,
+      Line::Switch(i, cases) => match conds.get(i as usize) {
+        Some(CondResult::Number(Some(n))) => missing_in(cases, *n),
+        _ => 0,
+      },
+      Line::Select(ref cond, cases) => {
+        let first = parsed.iter().find(|p| {
+          cases
+            .iter()
+            .any(|c| c.lines.iter().any(|cl| ptr::eq(cl, p.line)))
+        });
+
+        match first.map(|p| cond.evaluate(&lines[p.index])) {
+          Some(CondResult::Number(Some(n))) => missing_in(cases, n),
+          Some(_) => 0,
+          None => cases
+            .iter()
+            .map(|c| count_missing(c.lines, parsed, conds, lines))
+            .min()
+            .unwrap_or(0),
+        }
+      }
+      Line::Repeat(_, i) => match conds.get(i as usize) {
+        Some(CondResult::Number(Some(n))) => {
+          let count = parsed.iter().filter(|p| ptr::eq(p.line, l)).count();
+          n.saturating_sub(count)
+        }
+        _ => 0,
+      },
+      Line::RepeatGroup(ref g, i) => match conds.get(i as usize) {
+        Some(CondResult::Number(Some(n))) => g
+          .lines
+          .iter()
+          .filter(|gl| matches!(**gl, Line::Cells(_)))
+          .map(|gl| {
+            let count = parsed.iter().filter(|p| ptr::eq(p.line, gl)).count();
+            n.saturating_sub(count)
+          }).sum(),
+        _ => 0,
+      },
+      _ => 0,
+    }).sum()
+}
+
+/// Check the `$#` header above a line, if there is one
+fn check_header(pl: &ParsedLine, above: &[u8], diags: &mut Vec<Diagnostic>) {
+  let above = match str::from_utf8(above) {
+    Ok(a) if header::is_header(&a) => a,
+    _ => return,
+  };
+
+  for range in header::misplaced(above, pl.line) {
+    diags.push(Diagnostic {
+      line: pl.index - 1,
+      message: format!(
+        "Header entry '{}' does not end at the last column of a cell",
+        &above[range.clone()]
+      ),
+      range,
+      severity: Severity::Warning,
+    });
+  }
+}
+
+/// Check the cells of a single line. The units of the `settings` are shown
+/// for invalid numbers.
+fn check_cells(
+  card: &Card,
+  pl: &ParsedLine,
+  line: &[u8],
+  settings: &ModelSettings,
+  diags: &mut Vec<Diagnostic>,
+) {
+  for (i, pc) in pl.cells.iter().enumerate() {
+    let message = match (&pc.value, pc.cell) {
+      (&Value::Invalid(_), &Cell::Blank(_)) => Some((
+        "Text in a cell that should be blank".to_owned(),
+        Severity::Warning,
+      )),
+      (&Value::Invalid(s), cell) => {
+        let unit = card
+          .field(pl.line, i)
+          .and_then(|f| f.unit)
+          .and_then(|u| settings.unit(u));
+        Some((invalid_message(cell, s, unit), Severity::Error))
+      }
+      _ => None,
+    };
+
+    if let Some((message, severity)) = message {
+      diags.push(Diagnostic {
+        line: pl.index,
+        range: pc.range.start..pc.range.end.min(line.len()),
+        severity,
+        message,
+      });
+    }
+
+    // A value overflows if it fills its cell completely, and spills into the
+    // next cell (making it invalid) or beyond the last cell.
+    let numeric = matches!(
+      *pc.cell,
+      Cell::Integer(_)
+        | Cell::Float(_)
+        | Cell::IntegerorBlank(_)
+        | Cell::Binary(_)
+    );
+    let end = pc.range.end;
+    let spills = match pl.cells.get(i + 1) {
+      Some(next) => matches!(next.value, Value::Invalid(_)),
+      None => !line[end.min(line.len())..].trim_ascii().is_empty(),
+    };
+
+    if numeric
+      && spills
+      && end > pc.range.start
+      && end < line.len()
+      && line[end - 1] != b' '
+      && line[end] != b' '
+    {
+      diags.push(Diagnostic {
+        line: pl.index,
+        range: pc.range.clone(),
+        severity: Severity::Error,
+        message: format!(
+          "Value overflows its cell of width {}",
+          pc.cell.width()
+        ),
+      });
+    }
+  }
+}
+
+/// Check the cells of a control card line against the
+/// [`allowed_values`](::settings::allowed_values)
+fn check_allowed(card: &Card, pl: &ParsedLine, diags: &mut Vec<Diagnostic>) {
+  for (i, pc) in pl.cells.iter().enumerate() {
+    let value = match pc.value {
+      Value::Str(s) if !s.trim().is_empty() => s.trim(),
+      _ => continue,
+    };
+    let allowed = match settings::allowed_values(card.keyword, i) {
+      Some(a) if !a.contains(&value) => a,
+      _ => continue,
+    };
+    let name = card.field(pl.line, i).map_or("", |f| f.name);
+
+    diags.push(Diagnostic {
+      line: pl.index,
+      range: pc.range.clone(),
+      severity: Severity::Warning,
+      message: format!(
+        "Unknown {} '{}', expected one of {}",
+        name,
+        value,
+        allowed.join(", ")
+      ),
+    });
+  }
+}
+
+/// The message for a cell that could not be parsed. The `unit` of the cell
+/// is shown for numbers, if known.
+fn invalid_message(cell: &Cell, s: &str, unit: Option<String>) -> String {
+  let unit = unit.map(|u| format!(" in {}", u)).unwrap_or_default();
+
+  match *cell {
+    Cell::Integer(_) | Cell::IntegerorBlank(_) => {
+      format!("Expected an integer{}, found '{}'", unit, s.trim())
+    }
+    Cell::Float(_) => {
+      format!("Expected a float{}, found '{}'", unit, s.trim())
+    }
+    Cell::Fixed(f) => format!("Expected '{}', found '{}'", f, s.trim()),
+    Cell::Binary(_) => {
+      format!("Expected a sequence of 0 and 1, found '{}'", s.trim())
+    }
+    Cell::Cont => format!("Expected '&', found '{}'", s.trim()),
+    Cell::Blank(_) | Cell::Kw | Cell::Str(_) => {
+      format!("Invalid entry '{}'", s.trim())
+    }
+  }
+}
+
+/// Put the diagnostics of a [`Model`](::include::Model) into the location
+/// list of the current window. Each diagnostic comes with the index of its
+/// file, diagnostics of included files are shown with their file name.
+pub fn send_diagnostics(
+  model: &Model,
+  diags: &[(usize, Diagnostic)],
+  nvim: &mut Neovim,
+) -> Result<(), Error> {
+  let items = diags
+    .iter()
+    .map(|(file, d)| {
+      let kind = match d.severity {
+        Severity::Error => "E",
+        Severity::Warning => "W",
+      };
+
+      let mut item = vec![
+        (NvimValue::from("lnum"), NvimValue::from(d.line as u64 + 1)),
+        (NvimValue::from("col"), NvimValue::from(d.range.start as u64 + 1)),
+        (NvimValue::from("type"), NvimValue::from(kind)),
+        (NvimValue::from("text"), NvimValue::from(d.message.as_str())),
+      ];
+      if *file > 0 {
+        let path = model.files[*file].path.to_string_lossy();
+        item.push((NvimValue::from("filename"), NvimValue::from(&*path)));
+      }
+
+      NvimValue::Map(item)
+    }).collect();
+
+  nvim
+    .call_function(
+      "setloclist",
+      vec![NvimValue::from(0), NvimValue::Array(items)],
+    ).context("'setloclist' failed")?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use validation::{validate, Severity};
+
+  const LINES: [&str; 12] = [
+    "NODE  /        1              0.            50.5              0.",
+    "NODE  /        2             0.a            50.5              0.",
+    "NODE  /        3              0.            50.5123456789012345678",
+    "SHELL /     3129       1       1    2967    2971    2970",
+    "SHELL /     3130       1    1009    29x8    2971    2967",
+    "$Comment",
+    "invalid line here",
+    "",
+    "NSMAS /        1              0.                                    ",
+    "NAMX NSMAS / ->1",
+    "        ELE 123",
+    "        END",
+  ];
+
+  #[test]
+  fn validation_finds_problems() {
+    let diags = validate(&LINES);
+    let v: Vec<(usize, usize, Severity)> = diags
+      .iter()
+      .map(|d| (d.line, d.range.start, d.severity))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (1, 16, Severity::Error),
+        (2, 48, Severity::Error),
+        (4, 32, Severity::Error),
+        (6, 0, Severity::Warning),
+        (9, 0, Severity::Error),
+      ],
+      v
+    );
+    assert_eq!("Expected a float, found '0.a'", diags[0].message);
+    assert_eq!("Expected 'NAME', found 'NAMX'", diags[4].message);
+  }
+
+  const CARD_MASS_INCOMPLETE: [&str; 4] = [
+    "MASS  /        0       0                                                        ",
+    "NAME MASS  / ->1                                                                ",
+    "NODE  /        1              0.            50.5              0.",
+    "NODE  /        1              0.            50.5              0.",
+  ];
+
+  #[test]
+  fn validation_finds_incomplete_cards() {
+    let diags = validate(&CARD_MASS_INCOMPLETE);
+
+    assert_eq!(1, diags.len());
+    assert_eq!(0, diags[0].line);
+    assert_eq!("Card is incomplete, 1 line(s) missing", diags[0].message);
+  }
+
+  #[test]
+  fn validation_warns_about_blanks() {
+    let lines = [
+      "MASS  /        0       0",
+      "NAME MASS",
+      "              1.",
+      "junk                1.",
+    ];
+    let diags = validate(&lines);
+
+    assert_eq!(1, diags.len());
+    assert_eq!((3, 0..8), (diags[0].line, diags[0].range.clone()));
+    assert_eq!(Severity::Warning, diags[0].severity);
+  }
+
+  #[test]
+  fn validation_finds_unterminated_blocks() {
+    let lines = [
+      "RUNEND/  ",
+      " TIME      15.01",
+      "END_RUNEND",
+      "OCTRL /  ",
+      " THPOUTPUT    POINT     1000",
+      "NODE  /        1              0.            50.5              0.",
+      "CTRL  /  ",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, &str)> =
+      diags.iter().map(|d| (d.line, d.message.as_ref())).collect();
+
+    assert_eq!(
+      vec![
+        (3, "Block is not terminated by 'END_OCTRL'"),
+        (6, "Block is not terminated by 'END_CTRL'"),
+      ],
+      v
+    );
+  }
+
+  #[test]
+  fn validation_checks_switches() {
+    let lines = [
+      "MATER /        5     302              0.       0       0       0       0",
+      "                                                              1.       0       0",
+      "NAME Spotweld",
+      "        0.        0.        0.        0.        0.        0.",
+      "NODE  /        1              0.            50.5              0.",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, usize, &str)> = diags
+      .iter()
+      .map(|d| (d.line, d.range.start, d.message.as_ref()))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (3, 50, "Expected an integer, found '0.'"),
+        (0, 0, "Card is incomplete, 1 line(s) missing"),
+      ],
+      v
+    );
+  }
+
+  #[test]
+  fn validation_finds_missing_cases() {
+    let lines = [
+      "MATER /        7       1              0.       0       0       0       0",
+      "                                                              1.       0       0",
+      "NAME Steel",
+      "MATER /        8     302              0.       0       0       0       0",
+      "                                                              1.       0       0",
+      "NAME Spotweld",
+      "NODE  /        1              0.            50.5              0.",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, &str)> =
+      diags.iter().map(|d| (d.line, d.message.as_ref())).collect();
+
+    assert_eq!(
+      vec![
+        (0, "Card is incomplete, 3 line(s) missing"),
+        (3, "Card is incomplete, 2 line(s) missing"),
+      ],
+      v
+    );
+  }
+
+  #[test]
+  fn validation_counts_repeated_lines() {
+    let lines = [
+      "FUNCT /        3       5",
+      "NAME Too short",
+      "                  0.                  0.                  1.",
+      "NODE  /        1              0.            50.5              0.",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, &str)> =
+      diags.iter().map(|d| (d.line, d.message.as_ref())).collect();
+
+    assert_eq!(vec![(0, "Card is incomplete, 2 line(s) missing")], v);
+  }
+
+  #[test]
+  fn validation_checks_repeated_groups() {
+    use card::cell::Cell::*;
+    use card::keyword::Keyword::Node;
+    use card::line::Conditional::*;
+    use card::line::Line::*;
+    use card::line::LineGroup;
+    use card::Card;
+    use settings::ModelSettings;
+    use validation::check_card;
+
+    static REPEATED: Card = Card {
+      lines: &[
+        Provides(&[Kw, Integer(8)], Number(8..16)),
+        RepeatGroup(
+          LineGroup {
+            lines: &[Cells(&[Integer(8), Float(16)]), Cells(&[Str(24)])],
+            fields: &[],
+          },
+          0,
+        ),
+        RepeatUntil(
+          LineGroup {
+            lines: &[Cells(&[Integer(8), Integer(8)])],
+            fields: &[],
+          },
+          "END",
+        ),
+      ],
+      fields: &[],
+      ownfold: true,
+      keyword: Node,
+    };
+
+    let lines = [
+      "NODE  /        2",
+      "       1              1.",
+      "first",
+      "       2              2.",
+    ];
+    let mut diags = vec![];
+    let parsed = REPEATED.parse(&lines);
+    let settings = ModelSettings::default();
+    check_card(&REPEATED, &parsed, &lines, &settings, &mut diags);
+    let v: Vec<(usize, &str)> =
+      diags.iter().map(|d| (d.line, d.message.as_ref())).collect();
+
+    assert_eq!(
+      vec![
+        (0, "Card is incomplete, 1 line(s) missing"),
+        (0, "Repetition is not terminated by 'END'"),
+      ],
+      v
+    );
+  }
+
+  #[test]
+  fn validation_checks_headers() {
+    let lines = [
+      "$#         IDNOD               X               Y               Z",
+      "NODE  /        1              0.            50.5              0.",
+      "$#       IDNOD           X               Y",
+      "NODE  /        2              0.            50.5              0.",
+      "$ ordinary comment",
+      "NODE  /        3              0.            50.5              0.",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, usize, Severity)> = diags
+      .iter()
+      .map(|d| (d.line, d.range.start, d.severity))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (2, 9, Severity::Warning),
+        (2, 25, Severity::Warning),
+        (2, 41, Severity::Warning),
+      ],
+      v
+    );
+    assert_eq!(
+      "Header entry 'IDNOD' does not end at the last column of a cell",
+      diags[0].message
+    );
+  }
+
+  #[test]
+  fn validation_finds_overflows() {
+    let lines = [
+      "SHELL / 123456789      1    1009    2968    2971    2967",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, &str)> = diags
+      .iter()
+      .map(|d| (d.range.start, d.message.as_ref()))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (8, "Value overflows its cell of width 8"),
+        (16, "Expected an integer, found '9      1'"),
+      ],
+      v
+    );
+  }
+
+  #[test]
+  fn validation_uses_the_settings() {
+    let lines = [
+      "INPUTVERSION 2011",
+      "ANALYSIS EXPLICIT",
+      "SOLVER    CRUSH",
+      "UNIT       MM       KG     HOUR   KELVIN",
+      "NODE  /        1              0.            50.x              0.",
+    ];
+    let diags = validate(&lines);
+    let v: Vec<(usize, usize, &str)> = diags
+      .iter()
+      .map(|d| (d.line, d.range.start, d.message.as_ref()))
+      .collect();
+
+    assert_eq!(
+      vec![
+        (2, 8, "Unknown SOLVER 'CRUSH', expected one of CRASH, SAFE"),
+        (3, 22, "Unknown TIME 'HOUR', expected one of MS, S"),
+        (4, 32, "Expected a float in MM, found '50.x'"),
+      ],
+      v
+    );
+  }
+}